@@ -30,4 +30,25 @@ pub enum ContractError {
     ErrInvalidVote = 26,
     ErrInternalState = 27,
     ErrConfigMissing = 28,
+    ErrInsufficientEscrow = 29,
+    ErrUnderstaffed = 30,
+    ErrInvalidPenalty = 31,
+    ErrJurorBanned = 32,
+    ErrAppealClosed = 33,
+    ErrAppealNotAllowed = 34,
+    ErrNotDrawn = 35,
+    ErrNotRegistered = 36,
+    ErrAlreadyRegistered = 37,
+    ErrInsufficientStake = 38,
+    ErrVerifierMissing = 39,
+    ErrInvalidChoiceCount = 40,
+    ErrInvalidBps = 41,
+    ErrNothingToClaim = 42,
+    ErrSameParties = 43,
+    ErrNotDelegate = 44,
+    ErrTokenNotAllowed = 45,
+    ErrEvidenceOpen = 46,
+    ErrEvidenceClosed = 47,
+    ErrEvidenceFull = 48,
+    ErrTreasuryIsAdmin = 49,
 }
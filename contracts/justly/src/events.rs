@@ -0,0 +1,135 @@
+use crate::types::{DisputeStatus, RulingOutcome};
+use soroban_sdk::{contractevent, Address, BytesN, Symbol};
+
+#[contractevent(topics = ["TOK_ADD"], data_format = "single-value")]
+pub struct TokenAdded {
+    pub token: Address,
+}
+
+#[contractevent(topics = ["TOK_REM"], data_format = "single-value")]
+pub struct TokenRemoved {
+    pub token: Address,
+}
+
+#[contractevent(topics = ["CAT_ADD"], data_format = "single-value")]
+pub struct CategoryAdded {
+    pub name: Symbol,
+}
+
+#[contractevent(topics = ["CAT_REM"], data_format = "single-value")]
+pub struct CategoryRemoved {
+    pub name: Symbol,
+}
+
+#[contractevent(topics = ["DEL_ADD"], data_format = "single-value")]
+pub struct DelegateAdded {
+    pub delegate: Address,
+}
+
+#[contractevent(topics = ["DEL_REM"], data_format = "single-value")]
+pub struct DelegateRemoved {
+    pub delegate: Address,
+}
+
+#[contractevent(topics = ["CREATED"], data_format = "vec")]
+pub struct DisputeCreated {
+    #[topic]
+    pub dispute_id: u64,
+    pub claimer: Address,
+    pub defender: Address,
+    pub category: Symbol,
+    pub jurors_required: u32,
+}
+
+#[contractevent(topics = ["PAID"], data_format = "vec")]
+pub struct DisputePaid {
+    #[topic]
+    pub dispute_id: u64,
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["EVIDENCE"], data_format = "vec")]
+pub struct EvidenceSubmitted {
+    #[topic]
+    pub dispute_id: u64,
+    pub submitter: Address,
+    pub evidence_hash: BytesN<32>,
+}
+
+/// A status change, reported with the round it happened in.
+#[contractevent(topics = ["PHASE"], data_format = "vec")]
+pub struct PhaseChanged {
+    #[topic]
+    pub dispute_id: u64,
+    pub round: u32,
+    pub status: DisputeStatus,
+}
+
+/// A juror offered a seat, who has until `deadline_accept_seconds` to take it.
+#[contractevent(topics = ["DRAWN"], data_format = "vec")]
+pub struct JurorDrawn {
+    #[topic]
+    pub dispute_id: u64,
+    #[topic]
+    pub round: u32,
+    pub juror: Address,
+    pub deadline_accept_seconds: u64,
+}
+
+#[contractevent(topics = ["ASSIGNED"], data_format = "vec")]
+pub struct JurorAssigned {
+    #[topic]
+    pub dispute_id: u64,
+    #[topic]
+    pub round: u32,
+    pub juror: Address,
+    pub stake: i128,
+}
+
+#[contractevent(topics = ["COMMITTED"], data_format = "single-value")]
+pub struct VoteCommitted {
+    #[topic]
+    pub dispute_id: u64,
+    #[topic]
+    pub round: u32,
+    pub juror: Address,
+}
+
+#[contractevent(topics = ["REVEALED"], data_format = "vec")]
+pub struct VoteRevealed {
+    #[topic]
+    pub dispute_id: u64,
+    #[topic]
+    pub round: u32,
+    pub juror: Address,
+    pub vote: u32,
+}
+
+#[contractevent(topics = ["EXECUTED"], data_format = "single-value")]
+pub struct RoundExecuted {
+    #[topic]
+    pub dispute_id: u64,
+    #[topic]
+    pub round: u32,
+    pub outcome: RulingOutcome,
+}
+
+/// An amount credited out of a dispute's escrow, either to a claimable
+/// balance or to a juror's registered stake.
+#[contractevent(topics = ["PAYOUT"], data_format = "vec")]
+pub struct Payout {
+    #[topic]
+    pub dispute_id: u64,
+    pub to: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["CLAIMED"], data_format = "single-value")]
+pub struct Claimed {
+    #[topic]
+    pub account: Address,
+    #[topic]
+    pub token: Address,
+    pub amount: i128,
+}
@@ -1,14 +1,26 @@
 #![no_std]
 use error::ContractError;
-use sha2::{Digest, Sha256};
-use soroban_sdk::{contract, contractimpl, Address, Bytes, BytesN, Env, Symbol, Vec};
-use types::{Categories, Config, Dispute, DisputeStatus, TimeLimits, ULTRAHONK_CONTRACT_ADDRESS};
+use soroban_sdk::{
+    contract, contractimpl, panic_with_error, token::TokenClient, xdr::ToXdr, Address, Bytes,
+    BytesN, Env, Symbol, Vec, I256,
+};
+use types::{
+    Categories, CategorySettings, Config, CreateDisputeParams, Dispute, DisputeStatus, Evidence,
+    JurorOutcome, NonRevealPenalty, Round, RulingMode, RulingOutcome, TiePolicy, TimeLimits,
+    UnderstaffedPolicy, CLAIMER_CHOICE, DEFENDER_CHOICE, DRAW_ATTEMPTS_PER_SEAT, MAX_BPS,
+    MAX_CHOICES, MAX_EVIDENCE, MAX_JURORS, PROOF_HEADER_BYTES, QUEUE_SCAN_LIMIT,
+};
 
 mod error;
+mod events;
+mod registry;
 mod storage;
 mod types;
 mod xlm;
 
+#[cfg(test)]
+mod tests;
+
 mod ultrahonk_contract {
     soroban_sdk::contractimport!(file = "ultrahonk_soroban_contract.wasm");
 }
@@ -21,23 +33,38 @@ impl Justly {
     pub fn __constructor(
         env: Env,
         admin: Address,
-        min_pay_seconds: u64,
-        max_pay_seconds: u64,
-        min_commit_seconds: u64,
-        max_commit_seconds: u64,
-        min_reveal_seconds: u64,
-        max_reveal_seconds: u64,
+        treasury: Address,
+        min_limits: TimeLimits,
+        max_limits: TimeLimits,
     ) {
         admin.require_auth();
 
+        if treasury == admin {
+            panic_with_error!(&env, ContractError::ErrTreasuryIsAdmin);
+        }
+
         let config = Config {
             admin: admin.clone(),
-            min_pay_seconds,
-            max_pay_seconds,
-            min_commit_seconds,
-            max_commit_seconds,
-            min_reveal_seconds,
-            max_reveal_seconds,
+            min_pay_seconds: min_limits.pay_seconds,
+            max_pay_seconds: max_limits.pay_seconds,
+            min_evidence_seconds: min_limits.evidence_seconds,
+            max_evidence_seconds: max_limits.evidence_seconds,
+            min_commit_seconds: min_limits.commit_seconds,
+            max_commit_seconds: max_limits.commit_seconds,
+            min_reveal_seconds: min_limits.reveal_seconds,
+            max_reveal_seconds: max_limits.reveal_seconds,
+            understaffed_policy: UnderstaffedPolicy::Refund,
+            max_commit_extensions: 0,
+            commit_extension_seconds: 0,
+            non_reveal_penalty: NonRevealPenalty::FullSlash,
+            tie_policy: TiePolicy::Refund,
+            appeal_window_seconds: 0,
+            accept_window_seconds: 0,
+            unstake_cooldown_seconds: 0,
+            verifier: None,
+            split_tolerance_bps: 0,
+            fee_bps: 500,
+            treasury,
         };
 
         storage::set_config(&env, &config);
@@ -49,6 +76,219 @@ impl Justly {
         storage::set_dispute_counter(&env, 0u64);
     }
 
+    /// Sets what happens to a dispute whose panel is short at the commit deadline.
+    pub fn set_understaffed_policy(
+        env: Env,
+        policy: UnderstaffedPolicy,
+        max_commit_extensions: u32,
+        commit_extension_seconds: u64,
+    ) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        if policy != UnderstaffedPolicy::Refund
+            && (commit_extension_seconds < cfg.min_commit_seconds
+                || commit_extension_seconds > cfg.max_commit_seconds)
+        {
+            return Err(ContractError::ErrInvalidDeadline);
+        }
+
+        cfg.understaffed_policy = policy;
+        cfg.max_commit_extensions = max_commit_extensions;
+        cfg.commit_extension_seconds = commit_extension_seconds;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    pub fn set_non_reveal_penalty(
+        env: Env,
+        penalty: NonRevealPenalty,
+    ) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if let NonRevealPenalty::PartialSlash(bps) = penalty {
+            if bps > MAX_BPS {
+                return Err(ContractError::ErrInvalidPenalty);
+            }
+        }
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.non_reveal_penalty = penalty;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    pub fn set_tie_policy(env: Env, policy: TiePolicy) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.tie_policy = policy;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets how long a ruling can be appealed. Zero makes every ruling final.
+    pub fn set_appeal_window(env: Env, seconds: u64) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.appeal_window_seconds = seconds;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets the bounds on `TimeLimits::evidence_seconds`.
+    pub fn set_evidence_limits(env: Env, min: u64, max: u64) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        if min > max || max > cfg.max_commit_seconds {
+            return Err(ContractError::ErrInvalidDeadline);
+        }
+
+        cfg.min_evidence_seconds = min;
+        cfg.max_evidence_seconds = max;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets how long drawn jurors have to accept. Zero disables the draw.
+    pub fn set_accept_window(env: Env, seconds: u64) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        if seconds > cfg.max_commit_seconds {
+            return Err(ContractError::ErrInvalidDeadline);
+        }
+
+        cfg.accept_window_seconds = seconds;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets the verifier for categories with `verify_proofs` set.
+    pub fn set_verifier(env: Env, verifier: Address) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.verifier = Some(verifier);
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets how far from the median a split vote can be and stay coherent.
+    pub fn set_split_tolerance(env: Env, bps: u32) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if bps > MAX_BPS {
+            return Err(ContractError::ErrInvalidBps);
+        }
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.split_tolerance_bps = bps;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets the protocol fee taken from slashed pools, in basis points.
+    pub fn set_fee_bps(env: Env, bps: u32) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if bps > MAX_BPS {
+            return Err(ContractError::ErrInvalidBps);
+        }
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.fee_bps = bps;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Sets the account that receives protocol fees, which can't be the admin.
+    pub fn set_treasury(env: Env, treasury: Address) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        if treasury == cfg.admin {
+            return Err(ContractError::ErrTreasuryIsAdmin);
+        }
+
+        cfg.treasury = treasury;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    /// Allows disputes in `token`, with no arbitration fee until one is set.
+    pub fn add_token(env: Env, token: Address) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if storage::get_token_fee(&env, &token).is_some() {
+            return Err(ContractError::ErrAlreadyExists);
+        }
+
+        storage::set_token_fee(&env, &token, 0);
+        events::TokenAdded { token }.publish(&env);
+        Ok(())
+    }
+
+    /// Stops new disputes and registrations in `token`. Existing ones settle as usual.
+    pub fn remove_token(env: Env, token: Address) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if storage::get_token_fee(&env, &token).is_none() {
+            return Err(ContractError::ErrNotFound);
+        }
+
+        storage::remove_token(&env, &token);
+        events::TokenRemoved { token }.publish(&env);
+        Ok(())
+    }
+
+    pub fn is_token_allowed(env: Env, token: Address) -> bool {
+        storage::get_token_fee(&env, &token).is_some()
+    }
+
+    /// Sets the arbitration fee in `token` for disputes created afterwards.
+    pub fn set_arbitration_fee(env: Env, token: Address, fee: i128) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if storage::get_token_fee(&env, &token).is_none() {
+            return Err(ContractError::ErrTokenNotAllowed);
+        }
+
+        if fee < 0 {
+            return Err(ContractError::ErrInvalidAmount);
+        }
+
+        storage::set_token_fee(&env, &token, fee);
+        Ok(())
+    }
+
+    pub fn set_category_settings(
+        env: Env,
+        category: Symbol,
+        settings: CategorySettings,
+    ) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if !storage::has_category(&env, category.clone()) {
+            return Err(ContractError::ErrCategoryNotFound);
+        }
+
+        if let Some(ruling) = settings.default_ruling {
+            if ruling > MAX_CHOICES {
+                return Err(ContractError::ErrInvalidVote);
+            }
+        }
+
+        if settings.fee_bps.is_some_and(|bps| bps > MAX_BPS) {
+            return Err(ContractError::ErrInvalidBps);
+        }
+
+        storage::set_category_settings(&env, &category, &settings);
+        Ok(())
+    }
+
     pub fn add_category(env: Env, name: Symbol) -> Result<(), ContractError> {
         require_admin(&env)?;
 
@@ -57,8 +297,9 @@ impl Justly {
             return Err(ContractError::ErrAlreadyExists);
         }
 
-        cats.items.push_back(name);
+        cats.items.push_back(name.clone());
         storage::set_categories(&env, &cats);
+        events::CategoryAdded { name }.publish(&env);
         Ok(())
     }
 
@@ -84,26 +325,81 @@ impl Justly {
 
         cats.items = new_items;
         storage::set_categories(&env, &cats);
+        storage::remove_category_settings(&env, &name);
+        events::CategoryRemoved { name }.publish(&env);
         Ok(())
     }
 
-    pub fn create_dispute(
-        env: Env,
-        claimer: Address,
-        defender: Address,
-        meta_hash: BytesN<32>,
-        min_amount: i128,
-        max_amount: i128,
-        category: Symbol,
-        allowed_jurors: Option<Vec<Address>>,
-        jurors_required: u32,
-        limits: TimeLimits,
-    ) -> Result<u64, ContractError> {
+    /// Allows `delegate` to create disputes on behalf of any claimer.
+    pub fn add_delegate(env: Env, delegate: Address) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if storage::is_delegate(&env, &delegate) {
+            return Err(ContractError::ErrAlreadyExists);
+        }
+
+        storage::set_delegate(&env, &delegate, true);
+        events::DelegateAdded { delegate }.publish(&env);
+        Ok(())
+    }
+
+    pub fn remove_delegate(env: Env, delegate: Address) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        if !storage::is_delegate(&env, &delegate) {
+            return Err(ContractError::ErrNotFound);
+        }
+
+        storage::set_delegate(&env, &delegate, false);
+        events::DelegateRemoved { delegate }.publish(&env);
+        Ok(())
+    }
+
+    pub fn is_delegate(env: Env, account: Address) -> bool {
+        storage::is_delegate(&env, &account)
+    }
+
+    /// Opens a dispute, authorized by the claimer or an allowlisted delegate.
+    pub fn create_dispute(env: Env, params: CreateDisputeParams) -> Result<u64, ContractError> {
+        let CreateDisputeParams {
+            claimer,
+            defender,
+            delegate,
+            meta_hash,
+            min_amount,
+            max_amount,
+            category,
+            token,
+            allowed_jurors,
+            jurors_required,
+            ruling_mode,
+            num_choices,
+            limits,
+            default_to_payer,
+        } = params;
+
+        match delegate {
+            Some(delegate) => {
+                if !storage::is_delegate(&env, &delegate) {
+                    return Err(ContractError::ErrNotDelegate);
+                }
+                delegate.require_auth();
+            }
+            None => claimer.require_auth(),
+        }
+
+        if claimer == defender {
+            return Err(ContractError::ErrSameParties);
+        }
+
         if !storage::has_category(&env, category.clone()) {
             return Err(ContractError::ErrCategoryNotFound);
         }
 
-        if jurors_required < 5 || jurors_required > 101 || jurors_required % 2 == 0 {
+        let arbitration_fee =
+            storage::get_token_fee(&env, &token).ok_or(ContractError::ErrTokenNotAllowed)?;
+
+        if !(5..=MAX_JURORS).contains(&jurors_required) || jurors_required % 2 == 0 {
             return Err(ContractError::ErrInvalidJurorCount);
         }
 
@@ -111,12 +407,24 @@ impl Justly {
             return Err(ContractError::ErrInvalidAmounts);
         }
 
+        if ruling_mode == RulingMode::Choice
+            && !(DEFENDER_CHOICE..=MAX_CHOICES).contains(&num_choices)
+        {
+            return Err(ContractError::ErrInvalidChoiceCount);
+        }
+
         let cfg = storage::get_config(&env)?;
 
         if limits.pay_seconds < cfg.min_pay_seconds || limits.pay_seconds > cfg.max_pay_seconds {
             return Err(ContractError::ErrInvalidDeadline);
         }
 
+        if limits.evidence_seconds < cfg.min_evidence_seconds
+            || limits.evidence_seconds > cfg.max_evidence_seconds
+        {
+            return Err(ContractError::ErrInvalidDeadline);
+        }
+
         if limits.commit_seconds < cfg.min_commit_seconds
             || limits.commit_seconds > cfg.max_commit_seconds
         {
@@ -135,6 +443,15 @@ impl Justly {
             return Err(ContractError::ErrInvalidDeadline);
         }
 
+        // The evidence phase closes after the payment deadline and leaves
+        // part of the commit window open.
+        if limits.evidence_seconds > 0
+            && !(limits.pay_seconds <= limits.evidence_seconds
+                && limits.evidence_seconds < limits.commit_seconds)
+        {
+            return Err(ContractError::ErrInvalidDeadline);
+        }
+
         let id = storage::increment_dispute_counter(&env);
         let now = env.ledger().timestamp();
 
@@ -146,29 +463,54 @@ impl Justly {
             min_amount,
             max_amount,
             category,
+            token,
             allowed_jurors,
-            jurors_required,
+            ruling_mode,
+            num_choices,
+            default_to_payer,
 
             deadline_pay_seconds: now + limits.pay_seconds,
-            deadline_commit_seconds: now + limits.commit_seconds,
-            deadline_reveal_seconds: now + limits.reveal_seconds,
-
-            assigned_jurors: Vec::new(&env),
-            juror_stakes: Vec::new(&env),
+            deadline_evidence_seconds: if limits.evidence_seconds > 0 {
+                now + limits.evidence_seconds
+            } else {
+                0
+            },
+            commit_seconds: limits.commit_seconds,
+            reveal_seconds: limits.reveal_seconds,
 
-            commitments: Vec::new(&env),
-            revealed_votes: Vec::new(&env),
-            revealed_salts: Vec::new(&env),
+            round: 0,
+            deadline_appeal_seconds: 0,
 
             status: DisputeStatus::Created,
             claimer_paid: false,
             defender_paid: false,
             claimer_amount: 0,
             defender_amount: 0,
+            arbitration_fee,
+            escrowed: 0,
             winner: None,
+            outcome: RulingOutcome::Pending,
         };
 
+        let round = new_round(
+            &env,
+            jurors_required,
+            now + limits.commit_seconds,
+            now + limits.reveal_seconds,
+            None,
+            0,
+        );
+
         storage::set_dispute(&env, &dispute);
+        storage::set_round(&env, id, 0, &round);
+        events::DisputeCreated {
+            dispute_id: id,
+            claimer: dispute.claimer,
+            defender: dispute.defender,
+            category: dispute.category,
+            jurors_required,
+        }
+        .publish(&env);
         Ok(id)
     }
 
@@ -199,22 +541,49 @@ impl Justly {
             return Err(ContractError::ErrInvalidAmount);
         }
 
+        if (caller == dispute.claimer && dispute.claimer_paid)
+            || (caller == dispute.defender && dispute.defender_paid)
+        {
+            return Err(ContractError::ErrAlreadyPaid);
+        }
+
+        let total = amount + dispute.arbitration_fee;
+        TokenClient::new(&env, &dispute.token).transfer(
+            &caller,
+            env.current_contract_address(),
+            &total,
+        );
+        dispute.escrowed += total;
+
         if caller == dispute.claimer {
-            if dispute.claimer_paid {
-                return Err(ContractError::ErrAlreadyPaid);
-            }
             dispute.claimer_paid = true;
             dispute.claimer_amount = amount;
         } else {
-            if dispute.defender_paid {
-                return Err(ContractError::ErrAlreadyPaid);
-            }
             dispute.defender_paid = true;
             dispute.defender_amount = amount;
         }
 
+        events::DisputePaid {
+            dispute_id,
+            payer: caller,
+            amount,
+        }
+        .publish(&env);
+
         if dispute.claimer_paid && dispute.defender_paid {
-            dispute.status = DisputeStatus::Commit;
+            let status = if dispute.deadline_evidence_seconds > 0
+                && now <= dispute.deadline_evidence_seconds
+            {
+                DisputeStatus::Evidence
+            } else {
+                DisputeStatus::Commit
+            };
+            set_status(&env, &mut dispute, status);
+
+            let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
+            draw_jurors(&env, &dispute, &mut round)?;
+            storage::set_round(&env, dispute_id, dispute.round, &round);
+            enqueue_round(&env, &dispute);
         }
 
         storage::set_dispute(&env, &dispute);
@@ -225,6 +594,7 @@ impl Justly {
         env: Env,
         caller: Address,
         category: Symbol,
+        token: Address,
         stake_amount: i128,
     ) -> Result<(u64, Address), ContractError> {
         caller.require_auth();
@@ -233,60 +603,144 @@ impl Justly {
             return Err(ContractError::ErrCategoryNotFound);
         }
 
-        let mut eligible = Vec::new(&env);
-        let count = storage::get_dispute_counter(&env);
-
-        for i in 1..=count {
-            if let Ok(dispute) = storage::get_dispute(&env, i) {
-                if dispute.status == DisputeStatus::Commit
-                    && dispute.category == category
-                    && (dispute.assigned_jurors.len() as u32) < dispute.jurors_required
-                {
-                    if let Some(ref allowed) = dispute.allowed_jurors {
-                        if allowed.contains(&caller) {
-                            eligible.push_back(i);
-                        }
-                    } else {
-                        eligible.push_back(i);
-                    }
+        prune_queue(&env, &category, &token)?;
+
+        // Only the first few entries of the queue are looked at, so the cost
+        // does not depend on how many disputes were ever created. Rounds whose
+        // seats are still reserved for drawn jurors are moved from the head
+        // to the tail, so they can't hold up the rest of the queue. Rounds
+        // the caller already sits in stay where they are for other jurors.
+        let now = env.ledger().timestamp();
+        let start = storage::get_queue_head(&env, &category, &token);
+        let tail = storage::get_queue_tail(&env, &category, &token);
+        let mut head = start;
+        let mut index = start;
+        let mut scanned = 0;
+        let mut picked = None;
+
+        while index < tail && scanned < QUEUE_SCAN_LIMIT {
+            let (dispute_id, round_index) =
+                storage::get_queue_entry(&env, &category, &token, index)
+                    .ok_or(ContractError::ErrInternalState)?;
+            let at_head = index == head;
+            index += 1;
+
+            let Some((dispute, round)) = open_round(&env, dispute_id, round_index)? else {
+                if at_head {
+                    storage::remove_queue_entry(&env, &category, &token, head);
+                    head += 1;
+                }
+                scanned += 1;
+                continue;
+            };
+
+            scanned += 1;
+            if round.assigned_jurors.contains(&caller) {
+                continue;
+            }
+
+            if !round.drawn_jurors.is_empty() && now <= round.deadline_accept_seconds {
+                if at_head {
+                    storage::remove_queue_entry(&env, &category, &token, head);
+                    storage::enqueue_dispute(&env, &category, &token, dispute_id, round_index);
+                    head += 1;
                 }
+                continue;
             }
+
+            picked = Some((dispute, round));
+            break;
+        }
+
+        if head != start {
+            storage::set_queue_head(&env, &category, &token, head);
         }
 
-        if eligible.is_empty() {
-            return Err(ContractError::ErrNoAvailableDisputes);
+        let (mut dispute, mut round) = picked.ok_or(ContractError::ErrNoAvailableDisputes)?;
+        let dispute_id = dispute.id;
+
+        seat_juror(&env, &mut dispute, &mut round, &caller, stake_amount)?;
+
+        storage::set_dispute(&env, &dispute);
+        storage::set_round(&env, dispute_id, dispute.round, &round);
+        if round.assigned_jurors.len() >= round.jurors_required {
+            prune_queue(&env, &category, &token)?;
         }
+        Ok((dispute_id, caller))
+    }
+
+    /// Takes a seat in a specific dispute, e.g. to fill a private panel.
+    pub fn join_dispute(
+        env: Env,
+        caller: Address,
+        dispute_id: u64,
+        stake_amount: i128,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
 
-        let dispute_id = eligible.get(0).ok_or(ContractError::ErrInternalState)?;
         let mut dispute = storage::get_dispute(&env, dispute_id)?;
 
-        if stake_amount < dispute.min_amount || stake_amount > dispute.max_amount {
-            return Err(ContractError::ErrStakeOutOfRange);
+        if !is_seating(&dispute) {
+            return Err(ContractError::ErrNotActive);
         }
 
-        if (dispute.assigned_jurors.len() as u32) >= dispute.jurors_required {
-            return Err(ContractError::ErrDisputeFull);
+        let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
+        let now = env.ledger().timestamp();
+
+        if now > round.deadline_commit_seconds {
+            return Err(ContractError::ErrDeadlineReached);
         }
 
-        if let Some(ref allowed) = dispute.allowed_jurors {
-            if !allowed.contains(&caller) {
-                return Err(ContractError::ErrNotAllowedJuror);
-            }
+        if !round.drawn_jurors.is_empty()
+            && now <= round.deadline_accept_seconds
+            && !round.drawn_jurors.contains(&caller)
+        {
+            return Err(ContractError::ErrNotDrawn);
+        }
+
+        seat_juror(&env, &mut dispute, &mut round, &caller, stake_amount)?;
+
+        storage::set_dispute(&env, &dispute);
+        storage::set_round(&env, dispute_id, dispute.round, &round);
+        if round.assigned_jurors.len() >= round.jurors_required {
+            prune_queue(&env, &dispute.category, &dispute.token)?;
+        }
+        Ok(())
+    }
+
+    /// Takes a seat the caller was drawn for, before `deadline_accept_seconds`.
+    pub fn accept_draw(
+        env: Env,
+        caller: Address,
+        dispute_id: u64,
+        stake_amount: i128,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let mut dispute = storage::get_dispute(&env, dispute_id)?;
+
+        if !is_seating(&dispute) {
+            return Err(ContractError::ErrNotActive);
         }
 
-        if dispute.assigned_jurors.contains(&caller) {
-            return Err(ContractError::ErrAlreadyJuror);
+        let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
+
+        if !round.drawn_jurors.contains(&caller) {
+            return Err(ContractError::ErrNotDrawn);
         }
 
-        dispute.assigned_jurors.push_back(caller.clone());
-        dispute.juror_stakes.push_back(stake_amount);
+        if env.ledger().timestamp() > round.deadline_accept_seconds {
+            return Err(ContractError::ErrDeadlineReached);
+        }
 
-        dispute.commitments.push_back(None);
-        dispute.revealed_votes.push_back(None);
-        dispute.revealed_salts.push_back(None);
+        seat_juror(&env, &mut dispute, &mut round, &caller, stake_amount)?;
 
         storage::set_dispute(&env, &dispute);
-        Ok((dispute_id, caller))
+        storage::set_round(&env, dispute_id, dispute.round, &round);
+        if round.assigned_jurors.len() >= round.jurors_required {
+            prune_queue(&env, &dispute.category, &dispute.token)?;
+        }
+        Ok(())
     }
 
     pub fn commit_vote(
@@ -299,26 +753,34 @@ impl Justly {
 
         let mut dispute = storage::get_dispute(&env, dispute_id)?;
 
+        maybe_close_evidence_phase(&env, &mut dispute);
+
+        if dispute.status == DisputeStatus::Evidence {
+            return Err(ContractError::ErrEvidenceOpen);
+        }
+
         if dispute.status != DisputeStatus::Commit {
             return Err(ContractError::ErrVotingClosed);
         }
 
-        if !dispute.assigned_jurors.contains(&caller) {
+        let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
+
+        if !round.assigned_jurors.contains(&caller) {
             return Err(ContractError::ErrNotJuror);
         }
 
         let now = env.ledger().timestamp();
-        if now > dispute.deadline_commit_seconds {
+        if now > round.deadline_commit_seconds {
             return Err(ContractError::ErrVotingClosed);
         }
 
-        let idx = dispute
+        let idx = round
             .assigned_jurors
             .iter()
             .position(|addr| addr == caller)
             .ok_or(ContractError::ErrNotJuror)? as u32;
 
-        if dispute
+        if round
             .commitments
             .get(idx)
             .ok_or(ContractError::ErrInternalState)?
@@ -327,11 +789,17 @@ impl Justly {
             return Err(ContractError::ErrAlreadyVoted);
         }
 
-        dispute.commitments.set(idx, Some(commitment));
+        round.commitments.set(idx, Some(commitment));
+        events::VoteCommitted {
+            dispute_id,
+            round: dispute.round,
+            juror: caller,
+        }
+        .publish(&env);
 
         let mut all_committed = true;
-        for i in 0..dispute.commitments.len() {
-            if dispute
+        for i in 0..round.commitments.len() {
+            if round
                 .commitments
                 .get(i)
                 .ok_or(ContractError::ErrInternalState)?
@@ -341,11 +809,12 @@ impl Justly {
                 break;
             }
         }
-        let jurors_joined = dispute.assigned_jurors.len() as u32;
-        if all_committed && jurors_joined >= dispute.jurors_required {
-            dispute.status = DisputeStatus::Reveal;
+        let jurors_joined = round.assigned_jurors.len();
+        if all_committed && jurors_joined >= round.jurors_required {
+            set_status(&env, &mut dispute, DisputeStatus::Reveal);
         }
         storage::set_dispute(&env, &dispute);
+        storage::set_round(&env, dispute_id, dispute.round, &round);
         Ok(())
     }
 
@@ -361,29 +830,34 @@ impl Justly {
         caller.require_auth();
 
         let mut dispute = storage::get_dispute(&env, dispute_id)?;
+        let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
 
-        maybe_start_reveal_phase(&env, &mut dispute)?;
+        maybe_start_reveal_phase(&env, &mut dispute, &round)?;
+
+        if is_understaffed(&env, &dispute, &round) {
+            return Err(ContractError::ErrUnderstaffed);
+        }
 
         if dispute.status != DisputeStatus::Reveal {
             return Err(ContractError::ErrRevealPhaseNotStarted);
         }
 
         let now = env.ledger().timestamp();
-        if now > dispute.deadline_reveal_seconds {
+        if now > round.deadline_reveal_seconds {
             return Err(ContractError::ErrRevealClosed);
         }
 
-        if !dispute.assigned_jurors.contains(&caller) {
+        if !round.assigned_jurors.contains(&caller) {
             return Err(ContractError::ErrNotJuror);
         }
 
-        let idx = dispute
+        let idx = round
             .assigned_jurors
             .iter()
             .position(|a| a == caller)
             .ok_or(ContractError::ErrNotJuror)? as u32;
 
-        if dispute
+        if round
             .revealed_votes
             .get(idx)
             .ok_or(ContractError::ErrInternalState)?
@@ -392,50 +866,67 @@ impl Justly {
             return Err(ContractError::ErrAlreadyVoted);
         }
 
-        let stored_commit = dispute
+        let stored_commit = round
             .commitments
             .get(idx)
             .ok_or(ContractError::ErrInternalState)?
             .ok_or(ContractError::ErrInvalidProof)?;
 
-        // // 1. Verify ZK proof via UltraHonk
-        // let addr = Address::from_str(&env, ULTRAHONK_CONTRACT_ADDRESS);
-        // let client = ultrahonk_contract::Client::new(&env, &addr);
-
-        // match client.try_verify_proof(&vk_json, &proof_blob) {
-        //     Ok(Ok(_)) => {}
-        //     _ => return Err(ContractError::ErrInvalidProof),
-        // }
-
-        // // 2. Verify SHA256(vote || salt) == commitment
-        let computed = compute_commitment(&env, vote, &salt)?;
+        // 1. Verify the vote and salt open the stored commitment
+        let computed = compute_commitment(&env, &dispute, dispute.round, &caller, vote, &salt)?;
         if computed != stored_commit {
             return Err(ContractError::ErrInvalidProof);
         }
 
+        // 2. Verify ZK proof via UltraHonk
+        if storage::get_category_settings(&env, &dispute.category).verify_proofs {
+            verify_reveal_proof(
+                &env,
+                dispute_id,
+                &caller,
+                &stored_commit,
+                &vk_json,
+                &proof_blob,
+            )?;
+        }
+
         // 3. Store reveal
-        dispute.revealed_votes.set(idx, Some(vote));
-        dispute.revealed_salts.set(idx, Some(salt));
+        round.revealed_votes.set(idx, Some(vote));
+        round.revealed_salts.set(idx, Some(salt));
+        events::VoteRevealed {
+            dispute_id,
+            round: dispute.round,
+            juror: caller,
+            vote,
+        }
+        .publish(&env);
 
         storage::set_dispute(&env, &dispute);
+        storage::set_round(&env, dispute_id, dispute.round, &round);
         Ok(())
     }
 
-    pub fn execute(env: Env, dispute_id: u64) -> Result<Address, ContractError> {
+    /// Closes the current round and returns its outcome.
+    pub fn execute(env: Env, dispute_id: u64) -> Result<RulingOutcome, ContractError> {
         let mut dispute = storage::get_dispute(&env, dispute_id)?;
+        let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
+
+        maybe_start_reveal_phase(&env, &mut dispute, &round)?;
 
-        maybe_start_reveal_phase(&env, &mut dispute)?;
+        if is_understaffed(&env, &dispute, &round) {
+            return Err(ContractError::ErrUnderstaffed);
+        }
 
         if dispute.status != DisputeStatus::Reveal {
             return Err(ContractError::ErrNotActive);
         }
 
         let now = env.ledger().timestamp();
-        let juror_count = dispute.assigned_jurors.len();
+        let juror_count = round.assigned_jurors.len();
 
         let mut all_revealed = true;
         for i in 0..juror_count {
-            if dispute
+            if round
                 .revealed_votes
                 .get(i)
                 .ok_or(ContractError::ErrInternalState)?
@@ -446,111 +937,339 @@ impl Justly {
             }
         }
 
-        if !all_revealed && now <= dispute.deadline_reveal_seconds {
+        if !all_revealed && now <= round.deadline_reveal_seconds {
             return Err(ContractError::ErrRevealNotFinished);
         }
 
-        let mut votes_claimer = 0;
-        let mut votes_defender = 0;
+        let cfg = storage::get_config(&env)?;
+        let can_appeal = round.jurors_required < MAX_JURORS;
 
+        let outcome = match dispute.ruling_mode {
+            RulingMode::Choice => choice_outcome(&env, &cfg, &dispute, &round, can_appeal)?,
+            RulingMode::Split => split_outcome(&env, &cfg, &round, can_appeal)?,
+        };
+
+        // Split votes are coherent within the tolerance of the median,
+        // choice votes only on the ruling itself.
+        let (target, tolerance) = match outcome {
+            RulingOutcome::Split(median) => (Some(median), cfg.split_tolerance_bps),
+            _ => (ruling_of(&outcome), 0),
+        };
+        let mut outcomes = Vec::new(&env);
         for i in 0..juror_count {
-            if let Some(vote) = dispute
-                .revealed_votes
-                .get(i)
-                .ok_or(ContractError::ErrInternalState)?
+            outcomes.push_back(juror_outcome(&round, i, target, tolerance)?);
+        }
+        round.juror_outcomes = outcomes;
+        round.outcome = outcome.clone();
+        events::RoundExecuted {
+            dispute_id,
+            round: dispute.round,
+            outcome: outcome.clone(),
+        }
+        .publish(&env);
+
+        match outcome {
+            RulingOutcome::Escalated => {
+                settle_round(&env, &cfg, &mut dispute, &round, false)?;
+                storage::set_round(&env, dispute_id, dispute.round, &round);
+                start_next_round(&env, &mut dispute, &round, None, 0)?;
+            }
+            RulingOutcome::Majority(_)
+            | RulingOutcome::DefaultRuling(_)
+            | RulingOutcome::Split(_)
+                if can_appeal && cfg.appeal_window_seconds > 0 =>
             {
-                if vote == 0 {
-                    votes_claimer += 1;
-                } else if vote == 1 {
-                    votes_defender += 1;
-                } else {
-                    return Err(ContractError::ErrInvalidVote);
-                }
+                set_status(&env, &mut dispute, DisputeStatus::Appealable);
+                dispute.deadline_appeal_seconds = now + cfg.appeal_window_seconds;
+                storage::set_round(&env, dispute_id, dispute.round, &round);
+            }
+            _ => {
+                settle_round(&env, &cfg, &mut dispute, &round, true)?;
+                storage::set_round(&env, dispute_id, dispute.round, &round);
             }
         }
 
-        let winner_vote = if votes_claimer > votes_defender { 0 } else { 1 };
+        storage::set_dispute(&env, &dispute);
+        prune_queue(&env, &dispute.category, &dispute.token)?;
+        Ok(outcome)
+    }
 
-        let mut correctness = Vec::new(&env);
-        for i in 0..juror_count {
-            let is_correct = match dispute
-                .revealed_votes
-                .get(i)
-                .ok_or(ContractError::ErrInternalState)?
-            {
-                Some(v) if v == winner_vote => 1,
-                _ => 0,
-            };
-            correctness.push_back(is_correct);
+    /// Appeals the current ruling for the same deposit. Returns the new round.
+    pub fn appeal(env: Env, caller: Address, dispute_id: u64) -> Result<u32, ContractError> {
+        caller.require_auth();
+
+        let mut dispute = storage::get_dispute(&env, dispute_id)?;
+
+        if dispute.status != DisputeStatus::Appealable {
+            return Err(ContractError::ErrNotActive);
         }
 
-        let mut total_slashed = 0i128;
+        let now = env.ledger().timestamp();
+        if now > dispute.deadline_appeal_seconds {
+            return Err(ContractError::ErrAppealClosed);
+        }
 
-        if winner_vote == 1 {
-            total_slashed += dispute.claimer_amount;
+        let round = storage::get_round(&env, dispute_id, dispute.round)?;
+        let ruling = ruling_of(&round.outcome);
+        let fee = if caller == dispute.claimer && ruling != Some(CLAIMER_CHOICE) {
+            dispute.claimer_amount
+        } else if caller == dispute.defender && ruling != Some(DEFENDER_CHOICE) {
+            dispute.defender_amount
         } else {
-            total_slashed += dispute.defender_amount;
+            return Err(ContractError::ErrAppealNotAllowed);
+        };
+
+        TokenClient::new(&env, &dispute.token).transfer(
+            &caller,
+            env.current_contract_address(),
+            &fee,
+        );
+        dispute.escrowed += fee;
+
+        let cfg = storage::get_config(&env)?;
+        settle_round(&env, &cfg, &mut dispute, &round, false)?;
+        start_next_round(&env, &mut dispute, &round, Some(caller), fee)?;
+
+        storage::set_dispute(&env, &dispute);
+        Ok(dispute.round)
+    }
+
+    /// Settles a dispute whose appeal window closed without an appeal.
+    pub fn finalize(env: Env, dispute_id: u64) -> Result<RulingOutcome, ContractError> {
+        let mut dispute = storage::get_dispute(&env, dispute_id)?;
+
+        if dispute.status != DisputeStatus::Appealable {
+            return Err(ContractError::ErrNotActive);
         }
 
-        for i in 0..juror_count {
-            if correctness.get(i).ok_or(ContractError::ErrInternalState)? == 0 {
-                total_slashed += dispute
-                    .juror_stakes
-                    .get(i)
-                    .ok_or(ContractError::ErrInternalState)?;
-            }
+        let now = env.ledger().timestamp();
+        if now <= dispute.deadline_appeal_seconds {
+            return Err(ContractError::ErrTooEarly);
         }
 
-        let admin_fee = total_slashed * 5 / 100;
-        let reward_pool = total_slashed - admin_fee;
+        let round = storage::get_round(&env, dispute_id, dispute.round)?;
+        let cfg = storage::get_config(&env)?;
+        settle_round(&env, &cfg, &mut dispute, &round, true)?;
 
-        let mut correct_count = 0;
-        for i in 0..juror_count {
-            if correctness.get(i).ok_or(ContractError::ErrInternalState)? == 1 {
-                correct_count += 1;
-            }
+        storage::set_dispute(&env, &dispute);
+        prune_queue(&env, &dispute.category, &dispute.token)?;
+        Ok(round.outcome)
+    }
+
+    /// Closes a dispute left unfunded past `deadline_pay_seconds`.
+    pub fn cancel_unfunded(env: Env, dispute_id: u64) -> Result<(), ContractError> {
+        let mut dispute = storage::get_dispute(&env, dispute_id)?;
+
+        if dispute.status != DisputeStatus::Created {
+            return Err(ContractError::ErrAlreadyFinished);
         }
 
-        let winners_total = correct_count + 1;
-        let reward_each = if winners_total > 0 {
-            reward_pool / (winners_total as i128)
-        } else {
-            0
+        let now = env.ledger().timestamp();
+        if now <= dispute.deadline_pay_seconds {
+            return Err(ContractError::ErrTooEarly);
+        }
+
+        if dispute.claimer_paid {
+            let claimer = dispute.claimer.clone();
+            let amount = dispute.claimer_amount + dispute.arbitration_fee;
+            release_escrow(&env, &mut dispute, &claimer, amount);
+        }
+
+        if dispute.defender_paid {
+            let defender = dispute.defender.clone();
+            let amount = dispute.defender_amount + dispute.arbitration_fee;
+            release_escrow(&env, &mut dispute, &defender, amount);
+        }
+
+        let default_winner = match (dispute.claimer_paid, dispute.defender_paid) {
+            (true, false) => Some((dispute.claimer.clone(), CLAIMER_CHOICE)),
+            (false, true) => Some((dispute.defender.clone(), DEFENDER_CHOICE)),
+            _ => None,
         };
 
-        let xlm_client = xlm::token_client(&env);
-        let contract_addr = env.current_contract_address();
-        let config = storage::get_config(&env)?;
+        match default_winner {
+            Some((winner, choice)) if dispute.default_to_payer => {
+                set_status(&env, &mut dispute, DisputeStatus::Finished);
+                dispute.winner = Some(winner);
+                dispute.outcome = RulingOutcome::DefaultRuling(choice);
+            }
+            _ => {
+                set_status(&env, &mut dispute, DisputeStatus::Cancelled);
+                dispute.outcome = RulingOutcome::Refunded;
+            }
+        }
+
+        storage::set_dispute(&env, &dispute);
+        Ok(())
+    }
 
-        if admin_fee > 0 {
-            let _ = xlm_client.try_transfer(&contract_addr, &config.admin, &admin_fee);
+    /// Applies the `UnderstaffedPolicy` and returns the policy actually applied.
+    pub fn resolve_understaffed(
+        env: Env,
+        dispute_id: u64,
+    ) -> Result<UnderstaffedPolicy, ContractError> {
+        let mut dispute = storage::get_dispute(&env, dispute_id)?;
+        let mut round = storage::get_round(&env, dispute_id, dispute.round)?;
+
+        maybe_close_evidence_phase(&env, &mut dispute);
+
+        if dispute.status != DisputeStatus::Commit
+            || round.assigned_jurors.len() >= round.jurors_required
+        {
+            return Err(ContractError::ErrNotActive);
+        }
+
+        let now = env.ledger().timestamp();
+        if now <= round.deadline_commit_seconds {
+            return Err(ContractError::ErrTooEarly);
         }
 
-        let winner = if winner_vote == 1 {
-            dispute.defender.clone()
+        let cfg = storage::get_config(&env)?;
+        let policy = if round.commit_extensions < cfg.max_commit_extensions {
+            cfg.understaffed_policy
         } else {
-            dispute.claimer.clone()
+            UnderstaffedPolicy::Refund
         };
 
-        if reward_each > 0 {
-            let _ = xlm_client.try_transfer(&contract_addr, &winner, &reward_each);
+        match policy {
+            UnderstaffedPolicy::Refund => {
+                if let Some(ref appellant) = round.appellant {
+                    release_escrow(&env, &mut dispute, appellant, round.appeal_fee);
+                }
 
-            for i in 0..juror_count {
-                if correctness.get(i).ok_or(ContractError::ErrInternalState)? == 1 {
-                    let juror = dispute
+                for i in 0..round.assigned_jurors.len() {
+                    let juror = round
                         .assigned_jurors
                         .get(i)
                         .ok_or(ContractError::ErrInternalState)?;
-                    let _ = xlm_client.try_transfer(&contract_addr, &juror, &reward_each);
+                    let stake = round
+                        .juror_stakes
+                        .get(i)
+                        .ok_or(ContractError::ErrInternalState)?;
+                    registry::release_stake(&env, &mut dispute, &juror, stake, stake)?;
+                }
+                round.outcome = RulingOutcome::Refunded;
+
+                // An appeal that never got its panel leaves the appealed
+                // ruling standing.
+                let previous = if dispute.round > 0 {
+                    Some(storage::get_round(&env, dispute_id, dispute.round - 1)?)
+                } else {
+                    None
+                };
+                match previous {
+                    Some(previous) if previous.outcome != RulingOutcome::Escalated => {
+                        settle_standing_ruling(&env, &cfg, &mut dispute, &previous)?;
+                    }
+                    _ => {
+                        let claimer = dispute.claimer.clone();
+                        let claimer_amount = dispute.claimer_amount + dispute.arbitration_fee;
+                        release_escrow(&env, &mut dispute, &claimer, claimer_amount);
+
+                        let defender = dispute.defender.clone();
+                        let defender_amount = dispute.defender_amount + dispute.arbitration_fee;
+                        release_escrow(&env, &mut dispute, &defender, defender_amount);
+
+                        set_status(&env, &mut dispute, DisputeStatus::Cancelled);
+                        dispute.outcome = RulingOutcome::Refunded;
+                    }
                 }
             }
+            UnderstaffedPolicy::ExtendCommit | UnderstaffedPolicy::Reopen => {
+                if policy == UnderstaffedPolicy::Reopen {
+                    release_idle_jurors(&env, &mut dispute, &mut round)?;
+                }
+
+                let reveal_window = round.deadline_reveal_seconds - round.deadline_commit_seconds;
+                round.deadline_commit_seconds = now + cfg.commit_extension_seconds;
+                round.deadline_reveal_seconds = round.deadline_commit_seconds + reveal_window;
+                round.commit_extensions += 1;
+            }
         }
 
-        dispute.status = DisputeStatus::Finished;
-        dispute.winner = Some(winner.clone());
         storage::set_dispute(&env, &dispute);
+        storage::set_round(&env, dispute_id, dispute.round, &round);
 
-        Ok(winner)
+        // The round may have been dropped from the queue once its commit
+        // deadline passed. A stale duplicate is harmless and gets pruned.
+        if dispute.status == DisputeStatus::Commit {
+            enqueue_round(&env, &dispute);
+        }
+        prune_queue(&env, &dispute.category, &dispute.token)?;
+        Ok(policy)
+    }
+
+    /// Appends `evidence_hash` to the evidence log during the evidence phase.
+    pub fn submit_evidence(
+        env: Env,
+        caller: Address,
+        dispute_id: u64,
+        evidence_hash: BytesN<32>,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        let dispute = storage::get_dispute(&env, dispute_id)?;
+
+        if caller != dispute.claimer && caller != dispute.defender {
+            return Err(ContractError::ErrUnauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        if dispute.status != DisputeStatus::Evidence || now > dispute.deadline_evidence_seconds {
+            return Err(ContractError::ErrEvidenceClosed);
+        }
+
+        let mut evidence = storage::get_evidence(&env, dispute_id);
+        if evidence.len() >= MAX_EVIDENCE {
+            return Err(ContractError::ErrEvidenceFull);
+        }
+
+        evidence.push_back(Evidence {
+            submitter: caller.clone(),
+            evidence_hash: evidence_hash.clone(),
+            submitted_at: now,
+        });
+        storage::set_evidence(&env, dispute_id, &evidence);
+        events::EvidenceSubmitted {
+            dispute_id,
+            submitter: caller,
+            evidence_hash,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Returns the evidence log of the dispute, oldest entry first.
+    pub fn get_evidence(env: Env, dispute_id: u64) -> Vec<Evidence> {
+        storage::get_evidence(&env, dispute_id)
+    }
+
+    /// Transfers everything settled disputes credited to `account` in `token`.
+    pub fn withdraw_claimable(
+        env: Env,
+        account: Address,
+        token: Address,
+    ) -> Result<i128, ContractError> {
+        account.require_auth();
+
+        let amount = storage::get_claimable(&env, &account, &token);
+        if amount == 0 {
+            return Err(ContractError::ErrNothingToClaim);
+        }
+
+        storage::add_claimable(&env, &account, &token, -amount);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &account, &amount);
+        events::Claimed {
+            account,
+            token,
+            amount,
+        }
+        .publish(&env);
+        Ok(amount)
+    }
+
+    pub fn get_claimable(env: Env, account: Address, token: Address) -> i128 {
+        storage::get_claimable(&env, &account, &token)
     }
 
     pub fn get_winner(env: Env, dispute_id: u64) -> Option<Address> {
@@ -565,6 +1284,30 @@ impl Justly {
     pub fn get_dispute(env: Env, dispute_id: u64) -> Result<Dispute, ContractError> {
         storage::get_dispute(&env, dispute_id)
     }
+
+    pub fn get_round(env: Env, dispute_id: u64, round: u32) -> Result<Round, ContractError> {
+        storage::get_round(&env, dispute_id, round)
+    }
+
+    /// Computes the commitment `reveal_vote` expects from `juror` for `vote`.
+    pub fn compute_commitment_view(
+        env: Env,
+        dispute_id: u64,
+        round: u32,
+        juror: Address,
+        vote: u32,
+        salt: BytesN<32>,
+    ) -> Result<BytesN<32>, ContractError> {
+        let dispute = storage::get_dispute(&env, dispute_id)?;
+        compute_commitment(&env, &dispute, round, &juror, vote, &salt)
+    }
+
+    /// Extends the TTL of a dispute and all of its rounds.
+    pub fn bump_dispute_ttl(env: Env, dispute_id: u64) -> Result<(), ContractError> {
+        let dispute = storage::get_dispute(&env, dispute_id)?;
+        storage::extend_dispute_ttl(&env, &dispute);
+        Ok(())
+    }
 }
 
 fn require_admin(env: &Env) -> Result<(), ContractError> {
@@ -573,26 +1316,840 @@ fn require_admin(env: &Env) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Moves the dispute to `status` and announces it with a `PHASE` event.
+fn set_status(env: &Env, dispute: &mut Dispute, status: DisputeStatus) {
+    dispute.status = status;
+    events::PhaseChanged {
+        dispute_id: dispute.id,
+        round: dispute.round,
+        status,
+    }
+    .publish(env);
+}
+
+/// Credits `amount` from the dispute escrow to the claimable balance of `to`.
+fn release_escrow(env: &Env, dispute: &mut Dispute, to: &Address, amount: i128) {
+    dispute.escrowed -= amount;
+    storage::add_claimable(env, to, &dispute.token, amount);
+    events::Payout {
+        dispute_id: dispute.id,
+        to: to.clone(),
+        amount,
+    }
+    .publish(env);
+}
+
+fn new_round(
+    env: &Env,
+    jurors_required: u32,
+    deadline_commit_seconds: u64,
+    deadline_reveal_seconds: u64,
+    appellant: Option<Address>,
+    appeal_fee: i128,
+) -> Round {
+    Round {
+        jurors_required,
+        drawn_jurors: Vec::new(env),
+        deadline_accept_seconds: 0,
+        deadline_commit_seconds,
+        deadline_reveal_seconds,
+        commit_extensions: 0,
+        assigned_jurors: Vec::new(env),
+        juror_stakes: Vec::new(env),
+        commitments: Vec::new(env),
+        revealed_votes: Vec::new(env),
+        revealed_salts: Vec::new(env),
+        juror_outcomes: Vec::new(env),
+        appellant,
+        appeal_fee,
+        outcome: RulingOutcome::Pending,
+    }
+}
+
+/// Opens the round after `prev` with `2n + 1` jurors, capped at `MAX_JURORS`.
+fn start_next_round(
+    env: &Env,
+    dispute: &mut Dispute,
+    prev: &Round,
+    appellant: Option<Address>,
+    appeal_fee: i128,
+) -> Result<(), ContractError> {
+    let now = env.ledger().timestamp();
+    let jurors_required = (prev.jurors_required * 2 + 1).min(MAX_JURORS);
+    let mut round = new_round(
+        env,
+        jurors_required,
+        now + dispute.commit_seconds,
+        now + dispute.reveal_seconds,
+        appellant,
+        appeal_fee,
+    );
+
+    dispute.round += 1;
+    set_status(env, dispute, DisputeStatus::Commit);
+    dispute.deadline_appeal_seconds = 0;
+    draw_jurors(env, dispute, &mut round)?;
+    storage::set_round(env, dispute.id, dispute.round, &round);
+    enqueue_round(env, dispute);
+    Ok(())
+}
+
+/// Queues the current round for `assign_dispute`, unless the panel is private.
+fn enqueue_round(env: &Env, dispute: &Dispute) {
+    if dispute.allowed_jurors.is_none() {
+        storage::enqueue_dispute(
+            env,
+            &dispute.category,
+            &dispute.token,
+            dispute.id,
+            dispute.round,
+        );
+    }
+}
+
+/// Loads a queued round if it can still take jurors.
+fn open_round(
+    env: &Env,
+    dispute_id: u64,
+    round_index: u32,
+) -> Result<Option<(Dispute, Round)>, ContractError> {
+    let dispute = storage::get_dispute(env, dispute_id)?;
+    if !is_seating(&dispute) || dispute.round != round_index {
+        return Ok(None);
+    }
+
+    let round = storage::get_round(env, dispute_id, round_index)?;
+    if env.ledger().timestamp() > round.deadline_commit_seconds
+        || round.assigned_jurors.len() >= round.jurors_required
+    {
+        return Ok(None);
+    }
+    Ok(Some((dispute, round)))
+}
+
+/// Drops entries from the head of the queue that can no longer take jurors.
+fn prune_queue(env: &Env, category: &Symbol, token: &Address) -> Result<(), ContractError> {
+    let mut head = storage::get_queue_head(env, category, token);
+    let tail = storage::get_queue_tail(env, category, token);
+    let start = head;
+
+    while head < tail {
+        let (dispute_id, round_index) = storage::get_queue_entry(env, category, token, head)
+            .ok_or(ContractError::ErrInternalState)?;
+        if open_round(env, dispute_id, round_index)?.is_some() {
+            break;
+        }
+        storage::remove_queue_entry(env, category, token, head);
+        head += 1;
+    }
+
+    if head != start {
+        storage::set_queue_head(env, category, token, head);
+    }
+    Ok(())
+}
+
+/// Draws up to `jurors_required` jurors by stake when the round opens.
+fn draw_jurors(env: &Env, dispute: &Dispute, round: &mut Round) -> Result<(), ContractError> {
+    let cfg = storage::get_config(env)?;
+    if cfg.accept_window_seconds == 0 {
+        return Ok(());
+    }
+
+    let now = env.ledger().timestamp();
+    match dispute.allowed_jurors {
+        Some(ref allowed) => draw_from_list(env, dispute, round, allowed, now)?,
+        None => draw_from_pool(env, dispute, round, now)?,
+    }
+
+    if round.drawn_jurors.is_empty() {
+        return Ok(());
+    }
+
+    round.deadline_accept_seconds = now + cfg.accept_window_seconds;
+    for juror in round.drawn_jurors.iter() {
+        events::JurorDrawn {
+            dispute_id: dispute.id,
+            round: dispute.round,
+            juror,
+            deadline_accept_seconds: round.deadline_accept_seconds,
+        }
+        .publish(env);
+    }
+    Ok(())
+}
+
+/// Stake `juror` could lock for the dispute, if it can be drawn at all.
+fn draw_weight(env: &Env, dispute: &Dispute, juror: &Address, now: u64) -> i128 {
+    if storage::get_juror_banned_until(env, juror) > now {
+        return 0;
+    }
+    let stake = match storage::get_juror_stake(env, &dispute.category, &dispute.token, juror) {
+        Some(record) => registry::available_stake(&record),
+        None => 0,
+    };
+    if stake >= dispute.min_amount {
+        stake
+    } else {
+        0
+    }
+}
+
+/// Picks jurors from the whole category pool by stake.
+fn draw_from_pool(
+    env: &Env,
+    dispute: &Dispute,
+    round: &mut Round,
+    now: u64,
+) -> Result<(), ContractError> {
+    let total = registry::pool_stake(env, &dispute.category, &dispute.token);
+    let mut attempts = round.jurors_required * DRAW_ATTEMPTS_PER_SEAT;
+    while round.drawn_jurors.len() < round.jurors_required && total > 0 && attempts > 0 {
+        attempts -= 1;
+        let ticket = draw_ticket(env, total as u128) as i128;
+        let juror = registry::pool_member_at(env, &dispute.category, &dispute.token, ticket)?;
+        if !round.drawn_jurors.contains(&juror) && draw_weight(env, dispute, &juror, now) > 0 {
+            round.drawn_jurors.push_back(juror);
+        }
+    }
+    Ok(())
+}
+
+/// Picks jurors from a private panel's `allowed_jurors` by stake.
+fn draw_from_list(
+    env: &Env,
+    dispute: &Dispute,
+    round: &mut Round,
+    allowed: &Vec<Address>,
+    now: u64,
+) -> Result<(), ContractError> {
+    let mut weights = Vec::new(env);
+    let mut total = 0i128;
+    for juror in allowed.iter() {
+        let weight = draw_weight(env, dispute, &juror, now);
+        weights.push_back(weight);
+        total += weight;
+    }
+
+    while round.drawn_jurors.len() < round.jurors_required && total > 0 {
+        let mut ticket = draw_ticket(env, total as u128) as i128;
+
+        for i in 0..allowed.len() {
+            let weight = weights.get(i).ok_or(ContractError::ErrInternalState)?;
+            if ticket < weight {
+                let juror = allowed.get(i).ok_or(ContractError::ErrInternalState)?;
+                round.drawn_jurors.push_back(juror);
+                weights.set(i, 0);
+                total -= weight;
+                break;
+            }
+            ticket -= weight;
+        }
+    }
+    Ok(())
+}
+
+/// Uniform ticket in `0..total`, drawn from two u64 halves when needed.
+fn draw_ticket(env: &Env, total: u128) -> u128 {
+    if let Ok(total) = u64::try_from(total) {
+        return env.prng().gen_range::<u64>(0..total) as u128;
+    }
+    let high = ((total - 1) >> 64) as u64;
+    loop {
+        let ticket = (u128::from(env.prng().gen_range::<u64>(0..=high)) << 64)
+            | u128::from(env.prng().gen::<u64>());
+        if ticket < total {
+            return ticket;
+        }
+    }
+}
+
+/// Seats `juror`, locking `stake_amount` of their registered stake.
+fn seat_juror(
+    env: &Env,
+    dispute: &mut Dispute,
+    round: &mut Round,
+    juror: &Address,
+    stake_amount: i128,
+) -> Result<(), ContractError> {
+    if stake_amount < dispute.min_amount || stake_amount > dispute.max_amount {
+        return Err(ContractError::ErrStakeOutOfRange);
+    }
+
+    if round.assigned_jurors.len() >= round.jurors_required {
+        return Err(ContractError::ErrDisputeFull);
+    }
+
+    if let Some(ref allowed) = dispute.allowed_jurors {
+        if !allowed.contains(juror) {
+            return Err(ContractError::ErrNotAllowedJuror);
+        }
+    }
+
+    if round.assigned_jurors.contains(juror) {
+        return Err(ContractError::ErrAlreadyJuror);
+    }
+
+    if storage::get_juror_banned_until(env, juror) > env.ledger().timestamp() {
+        return Err(ContractError::ErrJurorBanned);
+    }
+
+    registry::lock_stake(env, dispute, juror, stake_amount)?;
+
+    round.assigned_jurors.push_back(juror.clone());
+    round.juror_stakes.push_back(stake_amount);
+
+    round.commitments.push_back(None);
+    round.revealed_votes.push_back(None);
+    round.revealed_salts.push_back(None);
+    round.juror_outcomes.push_back(JurorOutcome::Pending);
+
+    events::JurorAssigned {
+        dispute_id: dispute.id,
+        round: dispute.round,
+        juror: juror.clone(),
+        stake: stake_amount,
+    }
+    .publish(env);
+    Ok(())
+}
+
+/// Pays out a closed round, and the parties too when it is the `final_round`.
+fn settle_round(
+    env: &Env,
+    cfg: &Config,
+    dispute: &mut Dispute,
+    round: &Round,
+    final_round: bool,
+) -> Result<(), ContractError> {
+    let ruling = ruling_of(&round.outcome);
+    let juror_count = round.assigned_jurors.len();
+
+    let mut total_slashed = round.appeal_fee;
+    let mut returned_deposits = 0;
+    if final_round {
+        match ruling {
+            Some(CLAIMER_CHOICE) => {
+                total_slashed += dispute.defender_amount;
+                returned_deposits = dispute.claimer_amount;
+            }
+            Some(DEFENDER_CHOICE) => {
+                total_slashed += dispute.claimer_amount;
+                returned_deposits = dispute.defender_amount;
+            }
+            _ => returned_deposits = dispute.claimer_amount + dispute.defender_amount,
+        }
+    }
+
+    // Incoherent jurors lose their whole stake. Jurors that never
+    // committed or never revealed fall under the non-reveal penalty.
+    let mut kept_stakes = Vec::new(env);
+    let mut coherent_stake = 0i128;
+    let mut returned_stakes = 0i128;
+
+    for i in 0..juror_count {
+        let juror = round
+            .assigned_jurors
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let stake = round
+            .juror_stakes
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+
+        let slashed = match round
+            .juror_outcomes
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?
+        {
+            JurorOutcome::Coherent => {
+                coherent_stake += stake;
+                0
+            }
+            JurorOutcome::NotCommitted | JurorOutcome::NotRevealed => {
+                apply_non_reveal_penalty(env, &cfg.non_reveal_penalty, &juror, stake)
+            }
+            _ => stake,
+        };
+
+        total_slashed += slashed;
+        returned_stakes += stake - slashed;
+        kept_stakes.push_back(stake - slashed);
+    }
+
+    // The arbitration fees are not subject to the protocol fee. With no
+    // coherent juror to reward the whole pool goes to the treasury.
+    let arbitration_pool = if final_round {
+        dispute.arbitration_fee * 2
+    } else {
+        0
+    };
+    let pool = total_slashed + arbitration_pool;
+    let reward_pool = if coherent_stake > 0 {
+        pool - total_slashed * fee_bps(env, cfg, &dispute.category) as i128 / MAX_BPS as i128
+    } else {
+        0
+    };
+
+    // Coherent jurors get their stake back plus their share of the pool,
+    // everyone else gets back whatever was not slashed.
+    let mut payouts = Vec::new(env);
+    let mut rewarded = 0i128;
+    for i in 0..juror_count {
+        let mut payout = kept_stakes.get(i).ok_or(ContractError::ErrInternalState)?;
+        if round
+            .juror_outcomes
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?
+            == JurorOutcome::Coherent
+        {
+            let stake = round
+                .juror_stakes
+                .get(i)
+                .ok_or(ContractError::ErrInternalState)?;
+            let reward = mul_div(env, reward_pool, stake, coherent_stake)?;
+            rewarded += reward;
+            payout += reward;
+        }
+        payouts.push_back(payout);
+    }
+    let treasury_amount = pool - rewarded;
+
+    // Payouts can only come out of deposits this dispute actually holds.
+    if pool + returned_stakes + returned_deposits > dispute.escrowed {
+        return Err(ContractError::ErrInsufficientEscrow);
+    }
+
+    if treasury_amount > 0 {
+        release_escrow(env, dispute, &cfg.treasury, treasury_amount);
+    }
+
+    if final_round {
+        award_deposits(env, dispute, round);
+    }
+
+    // Juror payouts are credited to the juror registry.
+    for i in 0..juror_count {
+        let juror = round
+            .assigned_jurors
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let stake = round
+            .juror_stakes
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let payout = payouts.get(i).ok_or(ContractError::ErrInternalState)?;
+        registry::release_stake(env, dispute, &juror, stake, payout)?;
+    }
+
+    Ok(())
+}
+
+/// Settles a dispute on an earlier round's ruling, paying that round's jurors.
+fn settle_standing_ruling(
+    env: &Env,
+    cfg: &Config,
+    dispute: &mut Dispute,
+    round: &Round,
+) -> Result<(), ContractError> {
+    let forfeited = match ruling_of(&round.outcome) {
+        Some(CLAIMER_CHOICE) => dispute.defender_amount,
+        Some(DEFENDER_CHOICE) => dispute.claimer_amount,
+        _ => 0,
+    };
+    let pool = forfeited + dispute.arbitration_fee * 2;
+    if dispute.claimer_amount + dispute.defender_amount + dispute.arbitration_fee * 2
+        > dispute.escrowed
+    {
+        return Err(ContractError::ErrInsufficientEscrow);
+    }
+
+    let mut coherent_stake = 0i128;
+    for i in 0..round.assigned_jurors.len() {
+        if round.juror_outcomes.get(i) == Some(JurorOutcome::Coherent) {
+            coherent_stake += round
+                .juror_stakes
+                .get(i)
+                .ok_or(ContractError::ErrInternalState)?;
+        }
+    }
+    let reward_pool = if coherent_stake > 0 {
+        pool - forfeited * fee_bps(env, cfg, &dispute.category) as i128 / MAX_BPS as i128
+    } else {
+        0
+    };
+
+    let mut rewarded = 0i128;
+    for i in 0..round.assigned_jurors.len() {
+        if round.juror_outcomes.get(i) != Some(JurorOutcome::Coherent) {
+            continue;
+        }
+        let juror = round
+            .assigned_jurors
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let stake = round
+            .juror_stakes
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let reward = mul_div(env, reward_pool, stake, coherent_stake)?;
+        if reward > 0 {
+            release_escrow(env, dispute, &juror, reward);
+        }
+        rewarded += reward;
+    }
+
+    if pool > rewarded {
+        release_escrow(env, dispute, &cfg.treasury, pool - rewarded);
+    }
+    award_deposits(env, dispute, round);
+    Ok(())
+}
+
+/// Pays the parties by the ruling of `round` and finishes the dispute.
+fn award_deposits(env: &Env, dispute: &mut Dispute, round: &Round) {
+    let ruling = ruling_of(&round.outcome);
+    let (claimer_amount, defender_amount) = match (ruling, &round.outcome) {
+        (Some(CLAIMER_CHOICE), _) => (dispute.claimer_amount, 0),
+        (Some(DEFENDER_CHOICE), _) => (0, dispute.defender_amount),
+        (_, RulingOutcome::Split(bps)) => {
+            let total = dispute.claimer_amount + dispute.defender_amount;
+            let award = total * *bps as i128 / MAX_BPS as i128;
+            (award, total - award)
+        }
+        _ => (dispute.claimer_amount, dispute.defender_amount),
+    };
+
+    if claimer_amount > 0 {
+        let claimer = dispute.claimer.clone();
+        release_escrow(env, dispute, &claimer, claimer_amount);
+    }
+    if defender_amount > 0 {
+        let defender = dispute.defender.clone();
+        release_escrow(env, dispute, &defender, defender_amount);
+    }
+
+    set_status(env, dispute, DisputeStatus::Finished);
+    dispute.winner = match ruling {
+        Some(CLAIMER_CHOICE) => Some(dispute.claimer.clone()),
+        Some(DEFENDER_CHOICE) => Some(dispute.defender.clone()),
+        _ => None,
+    };
+    dispute.outcome = round.outcome.clone();
+}
+
+/// Drops jurors that never committed and unlocks their stakes.
+fn release_idle_jurors(
+    env: &Env,
+    dispute: &mut Dispute,
+    round: &mut Round,
+) -> Result<(), ContractError> {
+    let mut assigned_jurors = Vec::new(env);
+    let mut juror_stakes = Vec::new(env);
+    let mut commitments = Vec::new(env);
+    let mut revealed_votes = Vec::new(env);
+    let mut revealed_salts = Vec::new(env);
+    let mut juror_outcomes = Vec::new(env);
+
+    for i in 0..round.assigned_jurors.len() {
+        let juror = round
+            .assigned_jurors
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let stake = round
+            .juror_stakes
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+        let commitment = round
+            .commitments
+            .get(i)
+            .ok_or(ContractError::ErrInternalState)?;
+
+        if commitment.is_none() {
+            registry::release_stake(env, dispute, &juror, stake, stake)?;
+            continue;
+        }
+
+        assigned_jurors.push_back(juror);
+        juror_stakes.push_back(stake);
+        commitments.push_back(commitment);
+        revealed_votes.push_back(None);
+        revealed_salts.push_back(None);
+        juror_outcomes.push_back(JurorOutcome::Pending);
+    }
+
+    round.assigned_jurors = assigned_jurors;
+    round.juror_stakes = juror_stakes;
+    round.commitments = commitments;
+    round.revealed_votes = revealed_votes;
+    round.revealed_salts = revealed_salts;
+    round.juror_outcomes = juror_outcomes;
+    Ok(())
+}
+
+/// Resolves a tie or a round without reveals according to the `TiePolicy`.
+fn tie_outcome(env: &Env, cfg: &Config, dispute: &Dispute, can_appeal: bool) -> RulingOutcome {
+    match cfg.tie_policy {
+        TiePolicy::Refund => RulingOutcome::Refunded,
+        TiePolicy::DefaultRuling => {
+            match storage::get_category_settings(env, &dispute.category).default_ruling {
+                Some(v) if v <= dispute.num_choices => RulingOutcome::DefaultRuling(v),
+                _ => RulingOutcome::Refunded,
+            }
+        }
+        TiePolicy::Appeal if can_appeal => RulingOutcome::Escalated,
+        TiePolicy::Appeal => RulingOutcome::Refunded,
+    }
+}
+
+/// Plurality of the revealed votes. Ties go to `tie_outcome`.
+fn choice_outcome(
+    env: &Env,
+    cfg: &Config,
+    dispute: &Dispute,
+    round: &Round,
+    can_appeal: bool,
+) -> Result<RulingOutcome, ContractError> {
+    let mut tally = Vec::new(env);
+    for _ in 0..=dispute.num_choices {
+        tally.push_back(0u32);
+    }
+
+    for vote in round.revealed_votes.iter().flatten() {
+        let count = tally.get(vote).ok_or(ContractError::ErrInvalidVote)?;
+        tally.set(vote, count + 1);
+    }
+
+    let mut leader = None;
+    let mut leader_votes = 0;
+    let mut tied = false;
+    for (choice, count) in tally.iter().enumerate() {
+        if count > leader_votes {
+            leader = Some(choice as u32);
+            leader_votes = count;
+            tied = false;
+        } else if count > 0 && count == leader_votes {
+            tied = true;
+        }
+    }
+
+    Ok(match leader {
+        Some(choice) if !tied => RulingOutcome::Majority(choice),
+        _ => tie_outcome(env, cfg, dispute, can_appeal),
+    })
+}
+
+/// Median of the revealed votes, rounded down for an even count.
+fn split_outcome(
+    env: &Env,
+    cfg: &Config,
+    round: &Round,
+    can_appeal: bool,
+) -> Result<RulingOutcome, ContractError> {
+    // Insertion sort, panels are at most `MAX_JURORS` wide.
+    let mut sorted: Vec<u32> = Vec::new(env);
+    for vote in round.revealed_votes.iter().flatten() {
+        if vote > MAX_BPS {
+            return Err(ContractError::ErrInvalidVote);
+        }
+        let mut at = sorted.len();
+        while at > 0 && sorted.get_unchecked(at - 1) > vote {
+            at -= 1;
+        }
+        sorted.insert(at, vote);
+    }
+
+    let n = sorted.len();
+    if n == 0 {
+        return Ok(match cfg.tie_policy {
+            TiePolicy::Appeal if can_appeal => RulingOutcome::Escalated,
+            _ => RulingOutcome::Refunded,
+        });
+    }
+
+    let median = if n % 2 == 1 {
+        sorted.get_unchecked(n / 2)
+    } else {
+        (sorted.get_unchecked(n / 2 - 1) + sorted.get_unchecked(n / 2)) / 2
+    };
+    Ok(RulingOutcome::Split(median))
+}
+
+/// `a * b / c`, through 256-bit math when the product overflows an i128.
+fn mul_div(env: &Env, a: i128, b: i128, c: i128) -> Result<i128, ContractError> {
+    if let Some(product) = a.checked_mul(b) {
+        return Ok(product / c);
+    }
+    I256::from_i128(env, a)
+        .mul(&I256::from_i128(env, b))
+        .div(&I256::from_i128(env, c))
+        .to_i128()
+        .ok_or(ContractError::ErrInternalState)
+}
+
+/// Protocol fee for disputes in `category`, in basis points.
+fn fee_bps(env: &Env, cfg: &Config, category: &Symbol) -> u32 {
+    storage::get_category_settings(env, category)
+        .fee_bps
+        .unwrap_or(cfg.fee_bps)
+}
+
+fn ruling_of(outcome: &RulingOutcome) -> Option<u32> {
+    match outcome {
+        RulingOutcome::Majority(v) | RulingOutcome::DefaultRuling(v) => Some(*v),
+        _ => None,
+    }
+}
+
+/// Classifies a juror against the round's ruling, within `tolerance`.
+fn juror_outcome(
+    round: &Round,
+    idx: u32,
+    ruling: Option<u32>,
+    tolerance: u32,
+) -> Result<JurorOutcome, ContractError> {
+    if round
+        .commitments
+        .get(idx)
+        .ok_or(ContractError::ErrInternalState)?
+        .is_none()
+    {
+        return Ok(JurorOutcome::NotCommitted);
+    }
+
+    match round
+        .revealed_votes
+        .get(idx)
+        .ok_or(ContractError::ErrInternalState)?
+    {
+        None => Ok(JurorOutcome::NotRevealed),
+        Some(v) if ruling.is_none_or(|r| r.abs_diff(v) <= tolerance) => Ok(JurorOutcome::Coherent),
+        Some(_) => Ok(JurorOutcome::Incoherent),
+    }
+}
+
+/// Part of `stake` slashed from a juror that failed to commit or reveal.
+fn apply_non_reveal_penalty(
+    env: &Env,
+    penalty: &NonRevealPenalty,
+    juror: &Address,
+    stake: i128,
+) -> i128 {
+    match penalty {
+        NonRevealPenalty::FullSlash => stake,
+        NonRevealPenalty::PartialSlash(bps) => stake * (*bps as i128) / (MAX_BPS as i128),
+        NonRevealPenalty::Cooldown(seconds) => {
+            let until = env.ledger().timestamp() + seconds;
+            storage::set_juror_banned_until(env, juror, until);
+            0
+        }
+    }
+}
+
+/// Jurors can take seats from the evidence phase until the commit deadline.
+fn is_seating(dispute: &Dispute) -> bool {
+    matches!(
+        dispute.status,
+        DisputeStatus::Evidence | DisputeStatus::Commit
+    )
+}
+
+/// Moves the dispute to its commit phase once the evidence deadline has passed.
+fn maybe_close_evidence_phase(env: &Env, dispute: &mut Dispute) {
+    if dispute.status == DisputeStatus::Evidence
+        && env.ledger().timestamp() > dispute.deadline_evidence_seconds
+    {
+        set_status(env, dispute, DisputeStatus::Commit);
+    }
+}
+
+/// Whether the commit deadline passed without a full panel.
+fn is_understaffed(env: &Env, dispute: &Dispute, round: &Round) -> bool {
+    dispute.status == DisputeStatus::Commit
+        && env.ledger().timestamp() > round.deadline_commit_seconds
+        && round.assigned_jurors.len() < round.jurors_required
+}
+
+/// Checks a reveal proof bound to the dispute, the juror and the commitment.
+fn verify_reveal_proof(
+    env: &Env,
+    dispute_id: u64,
+    juror: &Address,
+    commitment: &BytesN<32>,
+    vk_json: &Bytes,
+    proof_blob: &Bytes,
+) -> Result<(), ContractError> {
+    let cfg = storage::get_config(env)?;
+    let verifier = cfg.verifier.ok_or(ContractError::ErrVerifierMissing)?;
+
+    let expected = reveal_public_inputs(env, dispute_id, juror, commitment);
+    let end = PROOF_HEADER_BYTES + expected.len();
+    if proof_blob.len() < end || proof_blob.slice(PROOF_HEADER_BYTES..end) != expected {
+        return Err(ContractError::ErrInvalidProof);
+    }
+
+    let client = ultrahonk_contract::Client::new(env, &verifier);
+    match client.try_verify_proof(vk_json, proof_blob) {
+        Ok(Ok(_)) => Ok(()),
+        _ => Err(ContractError::ErrInvalidProof),
+    }
+}
+
+/// Public inputs a reveal proof is bound to, as 32-byte field elements.
+fn reveal_public_inputs(
+    env: &Env,
+    dispute_id: u64,
+    juror: &Address,
+    commitment: &BytesN<32>,
+) -> Bytes {
+    let mut id_field = [0u8; 32];
+    id_field[24..].copy_from_slice(&dispute_id.to_be_bytes());
+
+    let mut juror_field = env.crypto().sha256(&juror.clone().to_xdr(env)).to_array();
+    juror_field[0] = 0;
+
+    let mut commitment_field = commitment.to_array();
+    commitment_field[0] = 0;
+
+    let mut inputs = Bytes::from_array(env, &id_field);
+    inputs.extend_from_array(&juror_field);
+    inputs.extend_from_array(&commitment_field);
+    inputs
+}
+
+/// SHA-256 of `contract || dispute_id || round || juror || vote || salt`.
 fn compute_commitment(
     env: &Env,
+    dispute: &Dispute,
+    round: u32,
+    juror: &Address,
     vote: u32,
     salt: &BytesN<32>,
 ) -> Result<BytesN<32>, ContractError> {
-    if vote > 1 {
+    let max_vote = match dispute.ruling_mode {
+        RulingMode::Choice => dispute.num_choices,
+        RulingMode::Split => MAX_BPS,
+    };
+    if vote > max_vote {
         return Err(ContractError::ErrInvalidVote);
     }
 
-    let mut hasher = Sha256::new();
-    hasher.update(&vote.to_be_bytes());
-    hasher.update(&salt.to_array());
-    let hash = hasher.finalize();
-
-    let mut out = [0u8; 32];
-    out.copy_from_slice(&hash[..32]);
-    Ok(BytesN::from_array(env, &out))
+    let mut preimage = env.current_contract_address().to_xdr(env);
+    preimage.extend_from_array(&dispute.id.to_be_bytes());
+    preimage.extend_from_array(&round.to_be_bytes());
+    preimage.append(&juror.clone().to_xdr(env));
+    preimage.extend_from_array(&vote.to_be_bytes());
+    preimage.extend_from_array(&salt.to_array());
+    Ok(env.crypto().sha256(&preimage).to_bytes())
 }
 
-fn maybe_start_reveal_phase(env: &Env, dispute: &mut Dispute) -> Result<(), ContractError> {
+fn maybe_start_reveal_phase(
+    env: &Env,
+    dispute: &mut Dispute,
+    round: &Round,
+) -> Result<(), ContractError> {
+    maybe_close_evidence_phase(env, dispute);
+
     if dispute.status != DisputeStatus::Commit {
         return Ok(());
     }
@@ -600,8 +2157,8 @@ fn maybe_start_reveal_phase(env: &Env, dispute: &mut Dispute) -> Result<(), Cont
     let now = env.ledger().timestamp();
 
     let mut all_committed = true;
-    for i in 0..dispute.commitments.len() {
-        if dispute
+    for i in 0..round.commitments.len() {
+        if round
             .commitments
             .get(i)
             .ok_or(ContractError::ErrInternalState)?
@@ -612,8 +2169,14 @@ fn maybe_start_reveal_phase(env: &Env, dispute: &mut Dispute) -> Result<(), Cont
         }
     }
 
-    if now > dispute.deadline_commit_seconds || all_committed {
-        dispute.status = DisputeStatus::Reveal;
+    // A partial panel never moves on by itself, even if everyone on it
+    // committed early. Understaffed disputes go through `resolve_understaffed`.
+    if round.assigned_jurors.len() < round.jurors_required {
+        return Ok(());
+    }
+
+    if now > round.deadline_commit_seconds || all_committed {
+        set_status(env, dispute, DisputeStatus::Reveal);
     }
     Ok(())
 }
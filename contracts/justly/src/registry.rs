@@ -0,0 +1,339 @@
+use crate::error::ContractError;
+use crate::events;
+use crate::storage;
+use crate::types::{Dispute, JurorStake};
+use crate::{require_admin, Justly, JustlyArgs, JustlyClient};
+use soroban_sdk::{contractimpl, token::TokenClient, Address, Env, Symbol};
+
+/// Juror registry. Jurors stake per category and token ahead of time. Taking a seat in
+/// a dispute locks part of that stake, and whatever is left after the round
+/// is settled, plus any reward, is credited back to it.
+#[contractimpl]
+impl Justly {
+    /// Joins the juror pool of `category` with an initial stake in `token`,
+    /// which must be allowlisted.
+    pub fn register_juror(
+        env: Env,
+        caller: Address,
+        category: Symbol,
+        token: Address,
+        stake: i128,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if !storage::has_category(&env, category.clone()) {
+            return Err(ContractError::ErrCategoryNotFound);
+        }
+
+        if storage::get_token_fee(&env, &token).is_none() {
+            return Err(ContractError::ErrTokenNotAllowed);
+        }
+
+        if stake <= 0 {
+            return Err(ContractError::ErrInvalidAmount);
+        }
+
+        if storage::get_juror_stake(&env, &category, &token, &caller).is_some() {
+            return Err(ContractError::ErrAlreadyRegistered);
+        }
+
+        TokenClient::new(&env, &token).transfer(&caller, env.current_contract_address(), &stake);
+
+        join_pool(&env, &category, &token, &caller);
+
+        let record = JurorStake {
+            amount: stake,
+            locked: 0,
+            unstaking: 0,
+            unstake_at: 0,
+        };
+        store_stake(&env, &category, &token, &caller, &record);
+        Ok(())
+    }
+
+    /// Adds to the caller's registered stake in `category`.
+    pub fn top_up(
+        env: Env,
+        caller: Address,
+        category: Symbol,
+        token: Address,
+        amount: i128,
+    ) -> Result<(), ContractError> {
+        caller.require_auth();
+
+        if amount <= 0 {
+            return Err(ContractError::ErrInvalidAmount);
+        }
+
+        let mut record = storage::get_juror_stake(&env, &category, &token, &caller)
+            .ok_or(ContractError::ErrNotRegistered)?;
+
+        TokenClient::new(&env, &token).transfer(&caller, env.current_contract_address(), &amount);
+
+        record.amount += amount;
+        store_stake(&env, &category, &token, &caller, &record);
+        Ok(())
+    }
+
+    /// Starts the cooldown for withdrawing `amount` of free stake. The amount
+    /// stops counting towards draws and locks right away. A new request adds
+    /// to the pending amount and restarts the cooldown.
+    pub fn request_unstake(
+        env: Env,
+        caller: Address,
+        category: Symbol,
+        token: Address,
+        amount: i128,
+    ) -> Result<u64, ContractError> {
+        caller.require_auth();
+
+        let mut record = storage::get_juror_stake(&env, &category, &token, &caller)
+            .ok_or(ContractError::ErrNotRegistered)?;
+
+        if amount <= 0 {
+            return Err(ContractError::ErrInvalidAmount);
+        }
+
+        if amount > available_stake(&record) {
+            return Err(ContractError::ErrInsufficientStake);
+        }
+
+        let cfg = storage::get_config(&env)?;
+        record.unstaking += amount;
+        record.unstake_at = env.ledger().timestamp() + cfg.unstake_cooldown_seconds;
+        store_stake(&env, &category, &token, &caller, &record);
+        Ok(record.unstake_at)
+    }
+
+    /// Pays out the pending unstake once its cooldown has passed. A juror
+    /// with nothing left staked or locked leaves the pool.
+    pub fn withdraw(
+        env: Env,
+        caller: Address,
+        category: Symbol,
+        token: Address,
+    ) -> Result<i128, ContractError> {
+        caller.require_auth();
+
+        let mut record = storage::get_juror_stake(&env, &category, &token, &caller)
+            .ok_or(ContractError::ErrNotRegistered)?;
+
+        if record.unstaking == 0 {
+            return Err(ContractError::ErrInvalidAmount);
+        }
+
+        if env.ledger().timestamp() < record.unstake_at {
+            return Err(ContractError::ErrTooEarly);
+        }
+
+        let amount = record.unstaking;
+        record.amount -= amount;
+        record.unstaking = 0;
+        record.unstake_at = 0;
+
+        store_stake(&env, &category, &token, &caller, &record);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &caller, &amount);
+        Ok(amount)
+    }
+
+    /// Sets how long an unstake request waits before it can be withdrawn.
+    pub fn set_unstake_cooldown(env: Env, seconds: u64) -> Result<(), ContractError> {
+        require_admin(&env)?;
+
+        let mut cfg = storage::get_config(&env)?;
+        cfg.unstake_cooldown_seconds = seconds;
+        storage::set_config(&env, &cfg);
+        Ok(())
+    }
+
+    pub fn get_juror_stake(
+        env: Env,
+        juror: Address,
+        category: Symbol,
+        token: Address,
+    ) -> Result<JurorStake, ContractError> {
+        storage::get_juror_stake(&env, &category, &token, &juror)
+            .ok_or(ContractError::ErrNotRegistered)
+    }
+}
+
+/// Saves the juror's stake record and its weight in the pool, or takes the
+/// juror out of the pool once nothing is left staked, locked or waiting to be
+/// withdrawn.
+fn store_stake(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    juror: &Address,
+    record: &JurorStake,
+) {
+    let before = storage::get_juror_stake(env, category, token, juror)
+        .map(|old| available_stake(&old))
+        .unwrap_or(0);
+    if let Some(index) = storage::get_pool_index(env, category, token, juror) {
+        add_pool_stake(
+            env,
+            category,
+            token,
+            index,
+            available_stake(record) - before,
+        );
+    }
+
+    if record.amount > 0 || record.locked > 0 || record.unstaking > 0 {
+        storage::set_juror_stake(env, category, token, juror, record);
+        return;
+    }
+
+    leave_pool(env, category, token, juror);
+    storage::remove_juror_stake(env, category, token, juror);
+}
+
+// The pool keeps a Fenwick tree over the available stake of its slots, so
+// drawing a juror by stake or recording a stake change only touches a
+// logarithmic number of entries.
+
+/// Appends `juror` to the pool with no stake yet.
+fn join_pool(env: &Env, category: &Symbol, token: &Address, juror: &Address) {
+    // The new node covers the slots below it down to its lowest set bit.
+    let node = storage::get_pool_size(env, category, token) + 1;
+    let covered = stake_below(env, category, token, node - 1)
+        - stake_below(env, category, token, node - lowest_bit(node));
+    storage::set_pool_stake_node(env, category, token, node, covered);
+    storage::add_pool_member(env, category, token, juror);
+}
+
+/// Takes `juror` out of the pool. The last member moves into the freed slot
+/// and takes its stake along.
+fn leave_pool(env: &Env, category: &Symbol, token: &Address, juror: &Address) {
+    let Some(index) = storage::get_pool_index(env, category, token, juror) else {
+        return;
+    };
+    let last = storage::get_pool_size(env, category, token) - 1;
+    if index != last {
+        let moved = slot_stake(env, category, token, last);
+        add_pool_stake(env, category, token, index, moved);
+        add_pool_stake(env, category, token, last, -moved);
+    }
+    storage::remove_pool_member(env, category, token, juror);
+}
+
+fn lowest_bit(node: u32) -> u32 {
+    node & node.wrapping_neg()
+}
+
+fn add_pool_stake(env: &Env, category: &Symbol, token: &Address, index: u32, delta: i128) {
+    if delta == 0 {
+        return;
+    }
+    let size = storage::get_pool_size(env, category, token);
+    let mut node = index + 1;
+    while node <= size {
+        let value = storage::get_pool_stake_node(env, category, token, node);
+        storage::set_pool_stake_node(env, category, token, node, value + delta);
+        node += lowest_bit(node);
+    }
+}
+
+/// Combined stake of the first `count` slots.
+fn stake_below(env: &Env, category: &Symbol, token: &Address, count: u32) -> i128 {
+    let mut total = 0;
+    let mut node = count;
+    while node > 0 {
+        total += storage::get_pool_stake_node(env, category, token, node);
+        node -= lowest_bit(node);
+    }
+    total
+}
+
+fn slot_stake(env: &Env, category: &Symbol, token: &Address, index: u32) -> i128 {
+    stake_below(env, category, token, index + 1) - stake_below(env, category, token, index)
+}
+
+/// Available stake of the whole pool.
+pub(crate) fn pool_stake(env: &Env, category: &Symbol, token: &Address) -> i128 {
+    let size = storage::get_pool_size(env, category, token);
+    stake_below(env, category, token, size)
+}
+
+/// Pool member whose share of the pool stake holds `ticket`, which has to
+/// be below `pool_stake`.
+pub(crate) fn pool_member_at(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    mut ticket: i128,
+) -> Result<Address, ContractError> {
+    let size = storage::get_pool_size(env, category, token);
+    let mut index = 0;
+    let mut step = if size == 0 {
+        0
+    } else {
+        1 << (31 - size.leading_zeros())
+    };
+    while step > 0 {
+        let node = index + step;
+        if node <= size {
+            let value = storage::get_pool_stake_node(env, category, token, node);
+            if value <= ticket {
+                ticket -= value;
+                index = node;
+            }
+        }
+        step >>= 1;
+    }
+    storage::get_pool_member(env, category, token, index).ok_or(ContractError::ErrInternalState)
+}
+
+/// Registered stake that is neither locked in a dispute nor being unstaked.
+pub(crate) fn available_stake(record: &JurorStake) -> i128 {
+    record.amount - record.unstaking
+}
+
+/// Moves `amount` of the juror's free stake into the dispute escrow.
+pub(crate) fn lock_stake(
+    env: &Env,
+    dispute: &mut Dispute,
+    juror: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    let mut record = storage::get_juror_stake(env, &dispute.category, &dispute.token, juror)
+        .ok_or(ContractError::ErrNotRegistered)?;
+
+    if amount > available_stake(&record) {
+        return Err(ContractError::ErrInsufficientStake);
+    }
+
+    record.amount -= amount;
+    record.locked += amount;
+    dispute.escrowed += amount;
+    store_stake(env, &dispute.category, &dispute.token, juror, &record);
+    Ok(())
+}
+
+/// Unlocks a juror's `stake` and credits `payout` out of the dispute escrow
+/// back to the registry. Whatever part of the stake is not paid back stays in
+/// escrow as slashed, and a juror slashed down to nothing leaves the pool.
+/// The credit is announced with a `PAYOUT` event.
+pub(crate) fn release_stake(
+    env: &Env,
+    dispute: &mut Dispute,
+    juror: &Address,
+    stake: i128,
+    payout: i128,
+) -> Result<(), ContractError> {
+    let mut record = storage::get_juror_stake(env, &dispute.category, &dispute.token, juror)
+        .ok_or(ContractError::ErrInternalState)?;
+
+    record.locked -= stake;
+    record.amount += payout;
+    dispute.escrowed -= payout;
+    store_stake(env, &dispute.category, &dispute.token, juror, &record);
+    events::Payout {
+        dispute_id: dispute.id,
+        to: juror.clone(),
+        amount: payout,
+    }
+    .publish(env);
+    Ok(())
+}
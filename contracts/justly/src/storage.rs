@@ -1,10 +1,15 @@
 #![no_std]
 use crate::error::ContractError;
-use crate::types::{Categories, Config, Dispute, CATEGORIES_KEY, CONFIG_KEY, DISPUTE_COUNTER_KEY};
-use soroban_sdk::{BytesN, Env, Symbol, Vec};
+use crate::types::{
+    Categories, CategorySettings, Config, DataKey, Dispute, Evidence, JurorStake, Round,
+    CATEGORIES_KEY, CONFIG_KEY,
+};
+use justly_storage::{extend_instance, extend_persistent, get_persistent, set_persistent};
+use soroban_sdk::{Address, Env, Symbol, Vec};
 
 pub fn set_config(env: &Env, config: &Config) {
     env.storage().instance().set(CONFIG_KEY, config);
+    extend_instance(env);
 }
 
 pub fn get_config(env: &Env) -> Result<Config, ContractError> {
@@ -16,6 +21,7 @@ pub fn get_config(env: &Env) -> Result<Config, ContractError> {
 
 pub fn set_categories(env: &Env, categories: &Categories) {
     env.storage().instance().set(CATEGORIES_KEY, categories);
+    extend_instance(env);
 }
 
 pub fn get_categories(env: &Env) -> Categories {
@@ -31,15 +37,71 @@ pub fn has_category(env: &Env, category: Symbol) -> bool {
     get_categories(env).items.contains(&category)
 }
 
-pub fn set_dispute_counter(env: &Env, count: u64) {
-    env.storage().instance().set(DISPUTE_COUNTER_KEY, &count);
+pub fn set_category_settings(env: &Env, category: &Symbol, settings: &CategorySettings) {
+    env.storage()
+        .instance()
+        .set(&DataKey::CategorySettings(category.clone()), settings);
+    extend_instance(env);
 }
 
-pub fn get_dispute_counter(env: &Env) -> u64 {
+pub fn get_category_settings(env: &Env, category: &Symbol) -> CategorySettings {
+    env.storage()
+        .instance()
+        .get(&DataKey::CategorySettings(category.clone()))
+        .unwrap_or_default()
+}
+
+pub fn remove_category_settings(env: &Env, category: &Symbol) {
+    env.storage()
+        .instance()
+        .remove(&DataKey::CategorySettings(category.clone()));
+    extend_instance(env);
+}
+
+pub fn is_delegate(env: &Env, account: &Address) -> bool {
+    env.storage()
+        .instance()
+        .has(&DataKey::Delegate(account.clone()))
+}
+
+pub fn set_delegate(env: &Env, account: &Address, allowed: bool) {
+    let key = DataKey::Delegate(account.clone());
+    if allowed {
+        env.storage().instance().set(&key, &true);
+    } else {
+        env.storage().instance().remove(&key);
+    }
+    extend_instance(env);
+}
+
+/// Arbitration fee of an allowlisted token, `None` if the token is not
+/// allowed.
+pub fn get_token_fee(env: &Env, token: &Address) -> Option<i128> {
+    env.storage()
+        .instance()
+        .get(&DataKey::AllowedToken(token.clone()))
+}
+
+pub fn set_token_fee(env: &Env, token: &Address, fee: i128) {
     env.storage()
         .instance()
-        .get(DISPUTE_COUNTER_KEY)
-        .unwrap_or(0u64)
+        .set(&DataKey::AllowedToken(token.clone()), &fee);
+    extend_instance(env);
+}
+
+pub fn remove_token(env: &Env, token: &Address) {
+    env.storage()
+        .instance()
+        .remove(&DataKey::AllowedToken(token.clone()));
+    extend_instance(env);
+}
+
+pub fn set_dispute_counter(env: &Env, count: u64) {
+    set_persistent(env, &DataKey::DisputeCounter, &count);
+}
+
+pub fn get_dispute_counter(env: &Env) -> u64 {
+    get_persistent(env, &DataKey::DisputeCounter).unwrap_or(0u64)
 }
 
 pub fn increment_dispute_counter(env: &Env) -> u64 {
@@ -48,22 +110,245 @@ pub fn increment_dispute_counter(env: &Env) -> u64 {
     new_count
 }
 
-fn get_dispute_key(env: &Env, id: u64) -> BytesN<32> {
-    let mut arr = [0u8; 32];
-    arr[0..4].copy_from_slice(b"DISP");
-    arr[4..12].copy_from_slice(&id.to_be_bytes());
-    BytesN::from_array(env, &arr)
+pub fn set_dispute(env: &Env, dispute: &Dispute) {
+    set_persistent(env, &DataKey::Dispute(dispute.id), dispute);
 }
 
-pub fn set_dispute(env: &Env, dispute: &Dispute) {
+pub fn get_dispute(env: &Env, id: u64) -> Result<Dispute, ContractError> {
+    get_persistent(env, &DataKey::Dispute(id)).ok_or(ContractError::ErrNotFound)
+}
+
+pub fn set_round(env: &Env, dispute_id: u64, index: u32, round: &Round) {
+    set_persistent(env, &DataKey::Round(dispute_id, index), round);
+}
+
+pub fn get_round(env: &Env, dispute_id: u64, index: u32) -> Result<Round, ContractError> {
+    get_persistent(env, &DataKey::Round(dispute_id, index)).ok_or(ContractError::ErrNotFound)
+}
+
+/// Extends the TTL of a dispute, all of its rounds and the instance.
+pub fn extend_dispute_ttl(env: &Env, dispute: &Dispute) {
+    extend_instance(env);
+    extend_persistent(env, &DataKey::Dispute(dispute.id));
+    for index in 0..=dispute.round {
+        extend_persistent(env, &DataKey::Round(dispute.id, index));
+    }
+    let evidence = DataKey::Evidence(dispute.id);
+    if env.storage().persistent().has(&evidence) {
+        extend_persistent(env, &evidence);
+    }
+}
+
+pub fn get_evidence(env: &Env, dispute_id: u64) -> Vec<Evidence> {
+    get_persistent(env, &DataKey::Evidence(dispute_id)).unwrap_or(Vec::new(env))
+}
+
+pub fn set_evidence(env: &Env, dispute_id: u64, evidence: &Vec<Evidence>) {
+    set_persistent(env, &DataKey::Evidence(dispute_id), evidence);
+}
+
+pub fn get_queue_head(env: &Env, category: &Symbol, token: &Address) -> u64 {
+    get_persistent(env, &DataKey::QueueHead(category.clone(), token.clone())).unwrap_or(0u64)
+}
+
+pub fn set_queue_head(env: &Env, category: &Symbol, token: &Address, head: u64) {
+    set_persistent(
+        env,
+        &DataKey::QueueHead(category.clone(), token.clone()),
+        &head,
+    );
+}
+
+pub fn get_queue_tail(env: &Env, category: &Symbol, token: &Address) -> u64 {
+    get_persistent(env, &DataKey::QueueTail(category.clone(), token.clone())).unwrap_or(0u64)
+}
+
+pub fn get_queue_entry(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    index: u64,
+) -> Option<(u64, u32)> {
+    get_persistent(
+        env,
+        &DataKey::QueueEntry(category.clone(), token.clone(), index),
+    )
+}
+
+pub fn remove_queue_entry(env: &Env, category: &Symbol, token: &Address, index: u64) {
     env.storage()
-        .instance()
-        .set(&get_dispute_key(env, dispute.id), dispute);
+        .persistent()
+        .remove(&DataKey::QueueEntry(category.clone(), token.clone(), index));
 }
 
-pub fn get_dispute(env: &Env, id: u64) -> Result<Dispute, ContractError> {
+/// Appends a round to the tail of the category queue.
+pub fn enqueue_dispute(env: &Env, category: &Symbol, token: &Address, dispute_id: u64, round: u32) {
+    let tail = get_queue_tail(env, category, token);
+    set_persistent(
+        env,
+        &DataKey::QueueEntry(category.clone(), token.clone(), tail),
+        &(dispute_id, round),
+    );
+    set_persistent(
+        env,
+        &DataKey::QueueTail(category.clone(), token.clone()),
+        &(tail + 1),
+    );
+}
+
+pub fn get_pool_size(env: &Env, category: &Symbol, token: &Address) -> u32 {
+    get_persistent(env, &DataKey::PoolSize(category.clone(), token.clone())).unwrap_or(0u32)
+}
+
+pub fn get_pool_member(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    index: u32,
+) -> Option<Address> {
+    get_persistent(
+        env,
+        &DataKey::PoolMember(category.clone(), token.clone(), index),
+    )
+}
+
+pub fn get_pool_index(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    juror: &Address,
+) -> Option<u32> {
+    get_persistent(
+        env,
+        &DataKey::PoolIndex(category.clone(), token.clone(), juror.clone()),
+    )
+}
+
+pub fn get_pool_stake_node(env: &Env, category: &Symbol, token: &Address, node: u32) -> i128 {
+    get_persistent(
+        env,
+        &DataKey::PoolStake(category.clone(), token.clone(), node),
+    )
+    .unwrap_or(0i128)
+}
+
+pub fn set_pool_stake_node(env: &Env, category: &Symbol, token: &Address, node: u32, value: i128) {
+    set_persistent(
+        env,
+        &DataKey::PoolStake(category.clone(), token.clone(), node),
+        &value,
+    );
+}
+
+/// Appends `juror` to the pool of the category and token.
+pub fn add_pool_member(env: &Env, category: &Symbol, token: &Address, juror: &Address) {
+    let size = get_pool_size(env, category, token);
+    set_persistent(
+        env,
+        &DataKey::PoolMember(category.clone(), token.clone(), size),
+        juror,
+    );
+    set_persistent(
+        env,
+        &DataKey::PoolIndex(category.clone(), token.clone(), juror.clone()),
+        &size,
+    );
+    set_persistent(
+        env,
+        &DataKey::PoolSize(category.clone(), token.clone()),
+        &(size + 1),
+    );
+}
+
+/// Takes `juror` out of the pool, moving the last member into its slot.
+pub fn remove_pool_member(env: &Env, category: &Symbol, token: &Address, juror: &Address) {
+    let Some(index) = get_pool_index(env, category, token, juror) else {
+        return;
+    };
+    let last = get_pool_size(env, category, token) - 1;
+    if index != last {
+        if let Some(moved) = get_pool_member(env, category, token, last) {
+            set_persistent(
+                env,
+                &DataKey::PoolMember(category.clone(), token.clone(), index),
+                &moved,
+            );
+            set_persistent(
+                env,
+                &DataKey::PoolIndex(category.clone(), token.clone(), moved),
+                &index,
+            );
+        }
+    }
     env.storage()
-        .instance()
-        .get(&get_dispute_key(env, id))
-        .ok_or(ContractError::ErrNotFound)
+        .persistent()
+        .remove(&DataKey::PoolMember(category.clone(), token.clone(), last));
+    env.storage().persistent().remove(&DataKey::PoolIndex(
+        category.clone(),
+        token.clone(),
+        juror.clone(),
+    ));
+    set_persistent(
+        env,
+        &DataKey::PoolSize(category.clone(), token.clone()),
+        &last,
+    );
+}
+
+pub fn get_juror_stake(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    juror: &Address,
+) -> Option<JurorStake> {
+    get_persistent(
+        env,
+        &DataKey::JurorStake(category.clone(), token.clone(), juror.clone()),
+    )
+}
+
+pub fn set_juror_stake(
+    env: &Env,
+    category: &Symbol,
+    token: &Address,
+    juror: &Address,
+    stake: &JurorStake,
+) {
+    set_persistent(
+        env,
+        &DataKey::JurorStake(category.clone(), token.clone(), juror.clone()),
+        stake,
+    );
+}
+
+pub fn remove_juror_stake(env: &Env, category: &Symbol, token: &Address, juror: &Address) {
+    env.storage().persistent().remove(&DataKey::JurorStake(
+        category.clone(),
+        token.clone(),
+        juror.clone(),
+    ));
+}
+
+pub fn set_juror_banned_until(env: &Env, juror: &Address, until: u64) {
+    set_persistent(env, &DataKey::JurorBannedUntil(juror.clone()), &until);
+}
+
+pub fn get_juror_banned_until(env: &Env, juror: &Address) -> u64 {
+    get_persistent(env, &DataKey::JurorBannedUntil(juror.clone())).unwrap_or(0u64)
+}
+
+pub fn get_claimable(env: &Env, account: &Address, token: &Address) -> i128 {
+    get_persistent(env, &DataKey::Claimable(account.clone(), token.clone())).unwrap_or(0i128)
+}
+
+/// Adds `amount` to the account's claimable balance in `token`, or takes it
+/// off when negative. Empty balances are removed.
+pub fn add_claimable(env: &Env, account: &Address, token: &Address, amount: i128) {
+    let key = DataKey::Claimable(account.clone(), token.clone());
+    let balance = get_claimable(env, account, token) + amount;
+    if balance == 0 {
+        env.storage().persistent().remove(&key);
+    } else {
+        set_persistent(env, &key, &balance);
+    }
 }
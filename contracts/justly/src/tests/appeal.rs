@@ -0,0 +1,223 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, COMMIT_SECONDS, REVEAL_SECONDS};
+use crate::types::{DisputeStatus, JurorOutcome, RulingOutcome, TiePolicy, UnderstaffedPolicy};
+use crate::xlm::to_stroops;
+
+const APPEAL_WINDOW: u64 = 3600;
+
+#[test]
+fn test_ruling_is_appealable_then_finalized() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    let (dispute_id, claimer, _) = ctx.funded_dispute(to_stroops(40), |_| {});
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [1, 1, 1, 2, 2];
+    let outcome = ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    assert_eq!(outcome, RulingOutcome::Majority(1));
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Appealable);
+    assert_eq!(ctx.client.get_winner(&dispute_id), None);
+
+    let res = ctx.client.try_finalize(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    ctx.jump(APPEAL_WINDOW + 1);
+    assert_eq!(ctx.client.finalize(&dispute_id), RulingOutcome::Majority(1));
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(claimer));
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_only_losing_party_can_appeal_within_window() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    let (dispute_id, claimer, defender) = ctx.funded_dispute(to_stroops(40), |_| {});
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [1, 1, 1, 2, 2];
+    ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+
+    let res = ctx.client.try_appeal(&claimer, &dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrAppealNotAllowed)));
+
+    ctx.jump(APPEAL_WINDOW + 1);
+    let res = ctx.client.try_appeal(&defender, &dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrAppealClosed)));
+}
+
+#[test]
+fn test_appeal_settles_round_and_opens_larger_panel() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    let (dispute_id, _, defender) = ctx.funded_dispute(to_stroops(40), |_| {});
+    let first = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [1, 1, 1, 2, 2];
+    ctx.play_round(dispute_id, &first, |i| (to_stroops(10), votes[i], true));
+
+    assert_eq!(ctx.client.appeal(&defender, &dispute_id), 1);
+    assert_eq!(ctx.balance(&defender), to_stroops(920));
+
+    // Round 0 jurors are paid out without touching the parties' deposits.
+    let admin_fee = to_stroops(20) * 5 / 100;
+    let reward_each = (to_stroops(20) - admin_fee) / 3;
+    for i in 0..3 {
+        assert_eq!(
+            ctx.juror_stake(&first.get(i).unwrap()),
+            to_stroops(100) + reward_each
+        );
+    }
+    for i in 3..5 {
+        assert_eq!(ctx.juror_stake(&first.get(i).unwrap()), to_stroops(90));
+    }
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    let round = ctx.round(dispute_id);
+    let now = ctx.env.ledger().timestamp();
+    assert!(dispute.status == DisputeStatus::Commit);
+    assert_eq!(round.jurors_required, 11);
+    assert_eq!(round.appellant, Some(defender));
+    assert_eq!(round.appeal_fee, to_stroops(40));
+    assert_eq!(round.deadline_commit_seconds, now + COMMIT_SECONDS);
+    assert_eq!(round.deadline_reveal_seconds, now + REVEAL_SECONDS);
+
+    let previous = ctx.client.get_round(&dispute_id, &0);
+    assert_eq!(previous.outcome, RulingOutcome::Majority(1));
+    assert_eq!(
+        previous.juror_outcomes.get(3),
+        Some(JurorOutcome::Incoherent)
+    );
+    assert_eq!(ctx.escrow_balance(), dispute.escrowed);
+}
+
+#[test]
+fn test_last_round_ruling_is_final() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    let (dispute_id, claimer, defender) = ctx.funded_dispute(to_stroops(40), |_| {});
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [1, 1, 1, 2, 2];
+    ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    ctx.client.appeal(&defender, &dispute_id);
+
+    let second = generate_jurors(&ctx, 11, to_stroops(100));
+    let votes = [2, 2, 2, 2, 2, 2, 2, 1, 1, 1, 1];
+    let outcome = ctx.play_round(dispute_id, &second, |i| (to_stroops(10), votes[i], true));
+    assert_eq!(outcome, RulingOutcome::Majority(2));
+
+    ctx.jump(APPEAL_WINDOW + 1);
+    ctx.client.finalize(&dispute_id);
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(defender.clone()));
+
+    // The defender gets its deposit back. The appeal fee, the slashed stakes
+    // and the claimer's deposit are shared by the seven coherent jurors.
+    let pool = to_stroops(40) + to_stroops(40) + to_stroops(50);
+    let admin_fee = pool * 5 / 100;
+    let reward_each = (pool - admin_fee) / 7;
+    assert_eq!(ctx.balance(&claimer), to_stroops(950));
+    assert_eq!(ctx.balance(&defender), to_stroops(960));
+    for i in 0..7 {
+        assert_eq!(
+            ctx.juror_stake(&second.get(i).unwrap()),
+            to_stroops(100) + reward_each
+        );
+    }
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_understaffed_appeal_keeps_appealed_ruling() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    ctx.client
+        .set_arbitration_fee(&ctx.token.address, &to_stroops(3));
+    let (dispute_id, claimer, defender) = ctx.funded_dispute(to_stroops(40), |_| {});
+    let appealed = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [1, 1, 1, 2, 2];
+    ctx.play_round(dispute_id, &appealed, |i| (to_stroops(10), votes[i], true));
+    ctx.client.appeal(&defender, &dispute_id);
+
+    let jurors = generate_jurors(&ctx, 2, to_stroops(100));
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+    ctx.jump(COMMIT_SECONDS + 1);
+    let treasury_before = ctx.balance(&ctx.treasury);
+    assert_eq!(
+        ctx.client.resolve_understaffed(&dispute_id),
+        UnderstaffedPolicy::Refund
+    );
+
+    // The claimer keeps its win, the defender gets the appeal fee back but
+    // not its deposit.
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(dispute.outcome, RulingOutcome::Majority(1));
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(claimer.clone()));
+    assert_eq!(ctx.round(dispute_id).outcome, RulingOutcome::Refunded);
+    assert_eq!(ctx.balance(&claimer), to_stroops(997));
+    assert_eq!(ctx.balance(&defender), to_stroops(957));
+
+    // The appealed round's coherent jurors share the forfeited deposit and
+    // both arbitration fees, less the protocol fee on the deposit.
+    let each = to_stroops(44) / 3;
+    for (i, juror) in appealed.iter().enumerate() {
+        let reward = if i < 3 { each } else { 0 };
+        assert_eq!(ctx.client.get_claimable(&juror, &ctx.token.address), reward);
+    }
+    assert_eq!(
+        ctx.balance(&ctx.treasury),
+        treasury_before + to_stroops(46) - 3 * each
+    );
+    for juror in jurors.iter() {
+        assert_eq!(ctx.juror_stake(&juror), to_stroops(100));
+    }
+    assert_eq!(ctx.escrow_balance(), dispute.escrowed);
+}
+
+#[test]
+fn test_tie_escalates_to_new_round() {
+    let ctx = setup();
+    ctx.client.set_tie_policy(&TiePolicy::Appeal);
+    let (dispute_id, _, _) = ctx.funded_dispute(to_stroops(40), |_| {});
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+    for (i, vote) in [1, 2, 1, 2].iter().enumerate() {
+        ctx.commit(&jurors.get(i as u32).unwrap(), dispute_id, *vote, i as u8);
+    }
+    ctx.jump(COMMIT_SECONDS + 1);
+    for (i, vote) in [1, 2, 1, 2].iter().enumerate() {
+        ctx.reveal(&jurors.get(i as u32).unwrap(), dispute_id, *vote, i as u8);
+    }
+    ctx.jump(REVEAL_SECONDS);
+
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Escalated);
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Commit);
+    assert_eq!(dispute.round, 1);
+    assert_eq!(ctx.round(dispute_id).jurors_required, 11);
+    assert_eq!(ctx.round(dispute_id).appellant, None);
+    assert_eq!(dispute.escrowed, to_stroops(90));
+
+    let jurors = generate_jurors(&ctx, 11, to_stroops(100));
+    let votes = [1, 1, 1, 1, 1, 1, 2, 2, 2, 2, 2];
+    let outcome = ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    assert_eq!(outcome, RulingOutcome::Majority(1));
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Finished);
+}
@@ -0,0 +1,73 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::setup;
+use crate::xlm::to_stroops;
+use soroban_sdk::{testutils::Address as _, Address};
+
+#[test]
+fn test_claimer_authorizes_dispute() {
+    let ctx = setup();
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+
+    ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    let auths = ctx.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, claimer);
+
+    // Nobody else can open a dispute in the claimer's name.
+    ctx.env.set_auths(&[]);
+    let params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    assert!(ctx.client.try_create_dispute(&params).is_err());
+}
+
+#[test]
+fn test_parties_must_differ() {
+    let ctx = setup();
+    let claimer = Address::generate(&ctx.env);
+
+    let params = ctx.dispute_params(&claimer, &claimer, to_stroops(10), to_stroops(100), 5);
+    let res = ctx.client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrSameParties)));
+}
+
+#[test]
+fn test_allowlisted_delegate_creates_for_claimer() {
+    let ctx = setup();
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+    let delegate = Address::generate(&ctx.env);
+
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    params.delegate = Some(delegate.clone());
+    let res = ctx.client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotDelegate)));
+
+    ctx.client.add_delegate(&delegate);
+    assert!(ctx.client.is_delegate(&delegate));
+    let dispute_id = ctx.client.create_dispute(&params);
+    let auths = ctx.env.auths();
+    assert_eq!(auths.len(), 1);
+    assert_eq!(auths[0].0, delegate);
+    assert_eq!(ctx.client.get_dispute(&dispute_id).claimer, claimer);
+
+    ctx.client.remove_delegate(&delegate);
+    let res = ctx.client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotDelegate)));
+}
+
+#[test]
+fn test_delegate_allowlist_changes_are_checked() {
+    let ctx = setup();
+    let delegate = Address::generate(&ctx.env);
+
+    let res = ctx.client.try_remove_delegate(&delegate);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotFound)));
+
+    ctx.client.add_delegate(&delegate);
+    let res = ctx.client.try_add_delegate(&delegate);
+    assert_eq!(res, Err(Ok(ContractError::ErrAlreadyExists)));
+}
@@ -0,0 +1,138 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup};
+use crate::types::{DisputeStatus, JurorOutcome, RulingOutcome, MAX_CHOICES};
+use crate::xlm::to_stroops;
+use soroban_sdk::{Bytes, BytesN};
+
+const APPEAL_WINDOW: u64 = 3600;
+
+#[test]
+fn test_plurality_picks_choice_beyond_parties() {
+    let ctx = setup();
+    let (dispute_id, claimer, defender) =
+        ctx.funded_dispute(to_stroops(50), |params| params.num_choices = 3);
+    let claimer_before = ctx.balance(&claimer);
+    let defender_before = ctx.balance(&defender);
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [3, 1, 3, 2, 0];
+    let outcome = ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    assert_eq!(outcome, RulingOutcome::Majority(3));
+
+    // Choice 3 awards the dispute to neither party, so both deposits go back.
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(dispute.winner, None);
+    assert_eq!(ctx.balance(&claimer), claimer_before + to_stroops(50));
+    assert_eq!(ctx.balance(&defender), defender_before + to_stroops(50));
+
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.juror_outcomes.get(0), Some(JurorOutcome::Coherent));
+    assert_eq!(round.juror_outcomes.get(1), Some(JurorOutcome::Incoherent));
+    assert_eq!(round.juror_outcomes.get(4), Some(JurorOutcome::Incoherent));
+    assert_eq!(ctx.escrow_balance(), 0);
+}
+
+#[test]
+fn test_refusal_refunds_parties_and_rewards_coherent_jurors() {
+    let ctx = setup();
+    let (dispute_id, claimer, defender) =
+        ctx.funded_dispute(to_stroops(50), |params| params.num_choices = 2);
+    let claimer_before = ctx.balance(&claimer);
+    let defender_before = ctx.balance(&defender);
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [0, 0, 0, 1, 2];
+    let outcome = ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    assert_eq!(outcome, RulingOutcome::Majority(0));
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(dispute.winner, None);
+    assert_eq!(ctx.balance(&claimer), claimer_before + to_stroops(50));
+    assert_eq!(ctx.balance(&defender), defender_before + to_stroops(50));
+
+    let round = ctx.round(dispute_id);
+    let refusing = round.assigned_jurors.get(0).unwrap();
+    let dissenting = round.assigned_jurors.get(3).unwrap();
+    assert!(ctx.juror_stake(&refusing) > to_stroops(100));
+    assert_eq!(ctx.juror_stake(&dissenting), to_stroops(90));
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_plurality_tie_falls_back_to_tie_policy() {
+    let ctx = setup();
+    let (dispute_id, _, _) = ctx.funded_dispute(to_stroops(50), |params| params.num_choices = 3);
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [1, 1, 3, 3, 2];
+    let outcome = ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    assert_eq!(outcome, RulingOutcome::Refunded);
+}
+
+#[test]
+fn test_either_party_can_appeal_a_refusal() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    let (dispute_id, claimer, defender) =
+        ctx.funded_dispute(to_stroops(50), |params| params.num_choices = 2);
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let votes = [0, 0, 0, 1, 2];
+    ctx.play_round(dispute_id, &jurors, |i| (to_stroops(10), votes[i], true));
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Appealable);
+
+    let stranger = ctx.funded_account(to_stroops(100));
+    let res = ctx.client.try_appeal(&stranger, &dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrAppealNotAllowed)));
+
+    assert_eq!(ctx.client.appeal(&defender, &dispute_id), 1);
+    assert_eq!(ctx.round(dispute_id).appellant, Some(defender));
+    assert_ne!(ctx.round(dispute_id).appellant, Some(claimer));
+}
+
+#[test]
+fn test_choice_count_and_vote_range_are_checked() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+
+    for num_choices in [0, 1, MAX_CHOICES + 1] {
+        let mut params =
+            ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+        params.num_choices = num_choices;
+        let res = ctx.client.try_create_dispute(&params);
+        assert_eq!(res, Err(Ok(ContractError::ErrInvalidChoiceCount)));
+    }
+
+    // A juror can commit to anything, but a vote outside the dispute's
+    // choices never reveals.
+    let (dispute_id, _, _) = ctx.funded_dispute(to_stroops(50), |params| params.num_choices = 2);
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let salt = BytesN::from_array(&ctx.env, &[7; 32]);
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        ctx.client
+            .commit_vote(&juror, &dispute_id, &BytesN::from_array(&ctx.env, &[1; 32]));
+    }
+
+    let res = ctx.client.try_reveal_vote(
+        &jurors.get(0).unwrap(),
+        &dispute_id,
+        &3,
+        &salt,
+        &Bytes::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidVote)));
+}
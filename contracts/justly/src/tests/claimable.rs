@@ -0,0 +1,96 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::xlm::to_stroops;
+use soroban_sdk::{testutils::IssuerFlags, token::StellarAssetClient, Address};
+
+/// Creates, funds and decides a five-juror dispute for the claimer, both
+/// parties depositing 50.
+fn decided_for_claimer(ctx: &TestContext) -> (u64, Address, Address) {
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        ctx.commit(&juror, dispute_id, 1, i as u8);
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, 1, i as u8);
+    }
+    (dispute_id, claimer, defender)
+}
+
+#[test]
+fn test_execute_credits_claimable_balances() {
+    let ctx = setup();
+    let (dispute_id, claimer, _) = decided_for_claimer(&ctx);
+
+    ctx.client.execute(&dispute_id);
+
+    // Nothing is transferred until the winner withdraws.
+    let fee = to_stroops(50) * 5 / 100;
+    let owed = to_stroops(50);
+    assert_eq!(ctx.token.balance(&claimer), to_stroops(950));
+    assert_eq!(ctx.client.get_claimable(&claimer, &ctx.token.address), owed);
+    assert_eq!(
+        ctx.client.get_claimable(&ctx.treasury, &ctx.token.address),
+        fee
+    );
+
+    assert_eq!(
+        ctx.client.withdraw_claimable(&claimer, &ctx.token.address),
+        owed
+    );
+    assert_eq!(ctx.token.balance(&claimer), to_stroops(950) + owed);
+    assert_eq!(ctx.client.get_claimable(&claimer, &ctx.token.address), 0);
+
+    let res = ctx
+        .client
+        .try_withdraw_claimable(&claimer, &ctx.token.address);
+    assert_eq!(res, Err(Ok(ContractError::ErrNothingToClaim)));
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_blocked_recipient_keeps_its_claim() {
+    let ctx = setup();
+    let (dispute_id, claimer, _) = decided_for_claimer(&ctx);
+
+    // The winner can't receive tokens anymore, but the dispute still
+    // settles and everyone else can withdraw.
+    ctx.sac.issuer().set_flag(IssuerFlags::RevocableFlag);
+    let sac = StellarAssetClient::new(&ctx.env, &ctx.token.address);
+    sac.set_authorized(&claimer, &false);
+    ctx.client.execute(&dispute_id);
+
+    let owed = ctx.client.get_claimable(&claimer, &ctx.token.address);
+    assert!(owed > 0);
+    ctx.client
+        .withdraw_claimable(&ctx.treasury, &ctx.token.address);
+
+    assert!(ctx
+        .client
+        .try_withdraw_claimable(&claimer, &ctx.token.address)
+        .is_err());
+    assert_eq!(ctx.client.get_claimable(&claimer, &ctx.token.address), owed);
+
+    sac.set_authorized(&claimer, &true);
+    assert_eq!(
+        ctx.client.withdraw_claimable(&claimer, &ctx.token.address),
+        owed
+    );
+    assert_eq!(ctx.token.balance(&claimer), to_stroops(950) + owed);
+}
@@ -0,0 +1,108 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::xlm::to_stroops;
+use soroban_sdk::{Address, Bytes, BytesN, Vec};
+
+fn commitment(
+    ctx: &TestContext,
+    dispute_id: u64,
+    juror: &Address,
+    vote: u32,
+    salt_seed: u8,
+) -> BytesN<32> {
+    let salt = BytesN::from_array(&ctx.env, &[salt_seed; 32]);
+    ctx.client
+        .compute_commitment_view(&dispute_id, &0, juror, &vote, &salt)
+}
+
+/// Seats the same five jurors in two disputes.
+fn shared_panel(ctx: &TestContext) -> (u64, u64, Vec<Address>) {
+    let first = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let second = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    for juror in jurors.iter() {
+        ctx.client.join_dispute(&juror, &first, &to_stroops(10));
+        ctx.client.join_dispute(&juror, &second, &to_stroops(10));
+    }
+    (first, second, jurors)
+}
+
+#[test]
+fn test_commitment_binds_dispute_round_and_juror() {
+    let ctx = setup();
+    let (first, second, jurors) = shared_panel(&ctx);
+    let juror = jurors.get(0).unwrap();
+    let other = jurors.get(1).unwrap();
+    let salt = BytesN::from_array(&ctx.env, &[9; 32]);
+
+    let base = commitment(&ctx, first, &juror, 0, 9);
+    assert_ne!(base, commitment(&ctx, second, &juror, 0, 9));
+    assert_ne!(base, commitment(&ctx, first, &other, 0, 9));
+    assert_ne!(base, commitment(&ctx, first, &juror, 1, 9));
+    assert_ne!(
+        base,
+        ctx.client
+            .compute_commitment_view(&first, &1, &juror, &0, &salt)
+    );
+
+    let res = ctx
+        .client
+        .try_compute_commitment_view(&first, &0, &juror, &3, &salt);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidVote)));
+}
+
+#[test]
+fn test_commitment_replayed_across_disputes_fails() {
+    let ctx = setup();
+    let empty = Bytes::new(&ctx.env);
+    let (first, second, jurors) = shared_panel(&ctx);
+    let juror = jurors.get(0).unwrap();
+
+    // The juror reuses the commitment from the first dispute in the second.
+    for (i, j) in jurors.iter().enumerate() {
+        ctx.commit(&j, first, 0, i as u8);
+        if j == juror {
+            let replayed = commitment(&ctx, first, &j, 0, i as u8);
+            ctx.client.commit_vote(&j, &second, &replayed);
+        } else {
+            ctx.commit(&j, second, 0, i as u8);
+        }
+    }
+
+    assert_eq!(ctx.try_reveal(&juror, first, 0, 0, &empty, &empty), Ok(()));
+    assert_eq!(
+        ctx.try_reveal(&juror, second, 0, 0, &empty, &empty),
+        Err(ContractError::ErrInvalidProof)
+    );
+}
+
+#[test]
+fn test_commitment_copied_from_another_juror_fails() {
+    let ctx = setup();
+    let empty = Bytes::new(&ctx.env);
+    let (dispute_id, _, jurors) = shared_panel(&ctx);
+    let leader = jurors.get(0).unwrap();
+    let copier = jurors.get(1).unwrap();
+
+    // The copier submits the leader's commitment and later reveals with the
+    // leader's leaked vote and salt.
+    ctx.commit(&leader, dispute_id, 1, 0);
+    let copied = commitment(&ctx, dispute_id, &leader, 1, 0);
+    ctx.client.commit_vote(&copier, &dispute_id, &copied);
+    for (i, j) in jurors.iter().enumerate().skip(2) {
+        ctx.commit(&j, dispute_id, 0, i as u8);
+    }
+
+    assert_eq!(
+        ctx.try_reveal(&leader, dispute_id, 1, 0, &empty, &empty),
+        Ok(())
+    );
+    assert_eq!(
+        ctx.try_reveal(&copier, dispute_id, 1, 0, &empty, &empty),
+        Err(ContractError::ErrInvalidProof)
+    );
+}
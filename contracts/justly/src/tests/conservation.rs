@@ -0,0 +1,116 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::types::{DisputeStatus, RulingMode, MAX_BPS};
+use crate::xlm::to_stroops;
+use soroban_sdk::{Address, Vec};
+
+const APPEAL_WINDOW: u64 = 3600;
+
+/// Small deterministic generator, so failing cases can be replayed by seed.
+struct Lcg(u64);
+
+impl Lcg {
+    fn next(&mut self, below: u64) -> u64 {
+        self.0 = self
+            .0
+            .wrapping_mul(6_364_136_223_846_793_005)
+            .wrapping_add(1_442_695_040_888_963_407);
+        (self.0 >> 33) % below
+    }
+
+    /// Random amount in `[min, max]` whole tokens, down to the stroop.
+    fn amount(&mut self, min: u64, max: u64) -> i128 {
+        to_stroops(min) + self.next((to_stroops(max - min) + 1) as u64) as i128
+    }
+}
+
+/// Everything the accounts of a test own, in or out of the contract.
+fn total_held(ctx: &TestContext, parties: &[&Address], jurors: &Vec<Address>) -> i128 {
+    let mut total = ctx.balance(&ctx.treasury);
+    for party in parties {
+        total += ctx.balance(party);
+    }
+    for juror in jurors.iter() {
+        total += ctx.balance(&juror) + ctx.juror_stake(&juror);
+    }
+    total
+}
+
+/// Random stake and vote, missing the reveal roughly one time in five.
+fn random_ballot(rng: &mut Lcg, split: bool) -> (i128, u32, bool) {
+    let stake = rng.amount(1, 60);
+    let vote = if split {
+        rng.next(MAX_BPS as u64 + 1) as u32
+    } else {
+        rng.next(3) as u32
+    };
+    (stake, vote, rng.next(5) > 0)
+}
+
+/// Plays one dispute with random deposits, fees, stakes and votes, possibly
+/// through an appeal, and checks that every token paid in is paid out.
+fn check_conservation(seed: u64) {
+    let mut rng = Lcg(seed);
+    let ctx = setup();
+    ctx.client.set_fee_bps(&(rng.next(2_001) as u32));
+    ctx.client.set_arbitration_fee(
+        &ctx.token.address,
+        &(rng.next(to_stroops(1) as u64) as i128),
+    );
+    ctx.client.set_split_tolerance(&1_000);
+    if rng.next(2) == 1 {
+        ctx.client.set_appeal_window(&APPEAL_WINDOW);
+    }
+
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let first = generate_jurors(&ctx, 5, to_stroops(100));
+    let second = generate_jurors(&ctx, 11, to_stroops(100));
+    let mut jurors = first.clone();
+    jurors.append(&second);
+    let before = total_held(&ctx, &[&claimer, &defender], &jurors);
+
+    let split = rng.next(2) == 1;
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(1), to_stroops(100), 5);
+    if split {
+        params.ruling_mode = RulingMode::Split;
+    }
+    let dispute_id = ctx.client.create_dispute(&params);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &rng.amount(1, 100));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &rng.amount(1, 100));
+
+    ctx.play_round(dispute_id, &first, |_| random_ballot(&mut rng, split));
+
+    // The claimer can appeal anything but a ruling in its favour, the
+    // defender takes the rest.
+    if ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Appealable {
+        if ctx.client.try_appeal(&claimer, &dispute_id).is_err() {
+            ctx.client.appeal(&defender, &dispute_id);
+        }
+        ctx.play_round(dispute_id, &second, |_| random_ballot(&mut rng, split));
+        ctx.jump(APPEAL_WINDOW + 1);
+        ctx.client.finalize(&dispute_id);
+    }
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished, "seed {seed}");
+    assert_eq!(dispute.escrowed, 0, "seed {seed}");
+    assert_eq!(ctx.escrow_balance(), 0, "seed {seed}");
+    assert_eq!(
+        total_held(&ctx, &[&claimer, &defender], &jurors),
+        before,
+        "seed {seed}"
+    );
+}
+
+#[test]
+fn test_payouts_conserve_tokens() {
+    for seed in 0..16 {
+        check_conservation(seed);
+    }
+}
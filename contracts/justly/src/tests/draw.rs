@@ -0,0 +1,204 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::xlm::to_stroops;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    token::StellarAssetClient,
+    Address, Symbol, TryFromVal, Vec,
+};
+
+const ACCEPT_WINDOW: u64 = 600;
+
+/// Registers one juror per stake in the test category.
+fn register_pool(ctx: &TestContext, stakes: &[i128]) -> Vec<Address> {
+    let mut jurors = Vec::new(&ctx.env);
+    for stake in stakes.iter() {
+        jurors.append(&generate_jurors(ctx, 1, *stake));
+    }
+    jurors
+}
+
+fn drawn_event_count(ctx: &TestContext) -> u32 {
+    let drawn = Symbol::new(&ctx.env, "DRAWN");
+    let mut count = 0;
+    for (contract, topics, _) in ctx.env.events().all().iter() {
+        if contract != ctx.client.address {
+            continue;
+        }
+        let topic = topics
+            .get(0)
+            .and_then(|t| Symbol::try_from_val(&ctx.env, &t).ok());
+        if topic.as_ref() == Some(&drawn) {
+            count += 1;
+        }
+    }
+    count
+}
+
+#[test]
+fn test_funding_draws_panel_from_pool() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&ACCEPT_WINDOW);
+    let pool = register_pool(&ctx, &[to_stroops(100); 7]);
+
+    let dispute_id = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    assert_eq!(drawn_event_count(&ctx), 5);
+
+    let round = ctx.round(dispute_id);
+    let now = ctx.env.ledger().timestamp();
+    assert_eq!(round.drawn_jurors.len(), 5);
+    assert_eq!(round.deadline_accept_seconds, now + ACCEPT_WINDOW);
+    for (i, juror) in round.drawn_jurors.iter().enumerate() {
+        assert!(pool.contains(&juror));
+        assert_eq!(round.drawn_jurors.first_index_of(&juror), Some(i as u32));
+    }
+}
+
+#[test]
+fn test_seats_are_reserved_for_drawn_jurors() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&ACCEPT_WINDOW);
+    let pool = register_pool(&ctx, &[to_stroops(100); 6]);
+    let dispute_id = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+
+    let round = ctx.round(dispute_id);
+    let drawn = round.drawn_jurors.get(0).unwrap();
+    let skipped = pool
+        .iter()
+        .find(|juror| !round.drawn_jurors.contains(juror))
+        .unwrap();
+
+    let outsider = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let res = ctx.client.try_assign_dispute(
+        &outsider,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrNoAvailableDisputes)));
+
+    let res = ctx
+        .client
+        .try_accept_draw(&skipped, &dispute_id, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrNotDrawn)));
+
+    ctx.client.accept_draw(&drawn, &dispute_id, &to_stroops(10));
+    assert_eq!(ctx.round(dispute_id).assigned_jurors.get(0), Some(drawn));
+
+    // Once the window closes the remaining seats open up.
+    ctx.jump(ACCEPT_WINDOW + 1);
+    let late = round.drawn_jurors.get(1).unwrap();
+    let res = ctx
+        .client
+        .try_accept_draw(&late, &dispute_id, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrDeadlineReached)));
+
+    ctx.client.assign_dispute(
+        &outsider,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(ctx.round(dispute_id).assigned_jurors.len(), 2);
+}
+
+#[test]
+fn test_large_pool_still_fills_panel() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&ACCEPT_WINDOW);
+    let pool = register_pool(&ctx, &[to_stroops(100); 45]);
+
+    let dispute_id = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.drawn_jurors.len(), 5);
+    for (i, juror) in round.drawn_jurors.iter().enumerate() {
+        assert!(pool.contains(&juror));
+        assert_eq!(round.drawn_jurors.first_index_of(&juror), Some(i as u32));
+    }
+}
+
+#[test]
+fn test_draw_handles_18_decimal_stakes() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&ACCEPT_WINDOW);
+    let sac = ctx
+        .env
+        .register_stellar_asset_contract_v2(ctx.admin.clone());
+    ctx.client.add_token(&sac.address());
+    let unit = 10i128.pow(18);
+
+    let mut pool = Vec::new(&ctx.env);
+    for _ in 0..5 {
+        let juror = Address::generate(&ctx.env);
+        StellarAssetClient::new(&ctx.env, &sac.address()).mint(&juror, &(100 * unit));
+        ctx.client
+            .register_juror(&juror, &ctx.category, &sac.address(), &(100 * unit));
+        pool.push_back(juror);
+    }
+
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+    for party in [&claimer, &defender] {
+        StellarAssetClient::new(&ctx.env, &sac.address()).mint(party, &(50 * unit));
+    }
+    let mut params = ctx.dispute_params(&claimer, &defender, unit, 100 * unit, 5);
+    params.token = sac.address();
+    let dispute_id = ctx.client.create_dispute(&params);
+    ctx.client.pay_dispute(&claimer, &dispute_id, &(50 * unit));
+    ctx.client.pay_dispute(&defender, &dispute_id, &(50 * unit));
+
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.drawn_jurors.len(), 5);
+    for juror in pool.iter() {
+        assert!(round.drawn_jurors.contains(&juror));
+    }
+}
+
+#[test]
+fn test_draw_reaches_stake_anywhere_in_pool() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&ACCEPT_WINDOW);
+    // Stakes below the dispute's minimum cannot be drawn.
+    register_pool(&ctx, &[to_stroops(5); 60]);
+    let eligible = register_pool(&ctx, &[to_stroops(100_000); 5]);
+
+    let dispute_id = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.drawn_jurors.len(), 5);
+    for juror in eligible.iter() {
+        assert!(round.drawn_jurors.contains(&juror));
+    }
+}
+
+#[test]
+fn test_draw_is_weighted_by_stake() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&ACCEPT_WINDOW);
+    let heavy = register_pool(&ctx, &[to_stroops(100_000); 5]);
+    let light = register_pool(&ctx, &[to_stroops(10); 5]);
+
+    let dispute_id = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let round = ctx.round(dispute_id);
+    for juror in round.drawn_jurors.iter() {
+        assert!(heavy.contains(&juror));
+        assert!(!light.contains(&juror));
+    }
+}
+
+#[test]
+fn test_no_draw_when_accept_window_disabled() {
+    let ctx = setup();
+    register_pool(&ctx, &[to_stroops(100); 5]);
+
+    let dispute_id = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    assert!(ctx.round(dispute_id).drawn_jurors.is_empty());
+    assert_eq!(drawn_event_count(&ctx), 0);
+
+    let juror = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    ctx.client
+        .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+}
@@ -0,0 +1,216 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup};
+use crate::types::{DisputeStatus, RulingOutcome};
+use crate::xlm::to_stroops;
+
+#[test]
+fn test_pay_dispute_moves_deposits_into_escrow() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(40));
+    assert_eq!(ctx.balance(&claimer), to_stroops(960));
+    assert_eq!(ctx.escrow_balance(), to_stroops(40));
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert_eq!(dispute.escrowed, to_stroops(40));
+    assert!(dispute.status == DisputeStatus::Created);
+
+    let res = ctx
+        .client
+        .try_pay_dispute(&claimer, &dispute_id, &to_stroops(40));
+    assert_eq!(res, Err(Ok(ContractError::ErrAlreadyPaid)));
+    assert_eq!(ctx.balance(&claimer), to_stroops(960));
+
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(60));
+    assert_eq!(ctx.balance(&defender), to_stroops(940));
+    assert_eq!(ctx.escrow_balance(), to_stroops(100));
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert_eq!(dispute.escrowed, to_stroops(100));
+    assert!(dispute.status == DisputeStatus::Commit);
+}
+
+#[test]
+fn test_pay_dispute_fails_without_funds() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(5));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    let res = ctx
+        .client
+        .try_pay_dispute(&claimer, &dispute_id, &to_stroops(10));
+    assert!(res.is_err());
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(!dispute.claimer_paid);
+    assert_eq!(dispute.escrowed, 0);
+    assert_eq!(ctx.escrow_balance(), 0);
+}
+
+#[test]
+fn test_execute_pays_out_of_escrow() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.commit(&juror, dispute_id, 1, i as u8);
+    }
+    assert_eq!(ctx.escrow_balance(), to_stroops(150));
+
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, 1, i as u8);
+    }
+    assert_eq!(ctx.escrow_balance(), to_stroops(150));
+
+    let treasury_before = ctx.balance(&ctx.treasury);
+    let outcome = ctx.client.execute(&dispute_id);
+    assert_eq!(outcome, RulingOutcome::Majority(1));
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(claimer.clone()));
+
+    // The claimer gets its deposit back. The defender's deposit is the
+    // reward pool: 5% to the treasury and the rest split between the five
+    // coherent jurors.
+    let treasury_fee = to_stroops(50) * 5 / 100;
+    let reward_each = (to_stroops(50) - treasury_fee) / 5;
+
+    assert_eq!(ctx.balance(&ctx.treasury) - treasury_before, treasury_fee);
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.balance(&defender), to_stroops(950));
+    for juror in jurors.iter() {
+        assert_eq!(ctx.juror_stake(&juror), to_stroops(100) + reward_each);
+    }
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(dispute.escrowed, 0);
+    assert_eq!(ctx.escrow_balance(), 0);
+}
+
+#[test]
+fn test_assign_dispute_locks_registered_stake() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let juror = generate_jurors(&ctx, 1, to_stroops(30)).get(0).unwrap();
+    let poor_juror = generate_jurors(&ctx, 1, to_stroops(5)).get(0).unwrap();
+    let outsider = ctx.funded_account(to_stroops(100));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    ctx.client
+        .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(20));
+    assert_eq!(ctx.juror_stake(&juror), to_stroops(10));
+    assert_eq!(ctx.balance(&juror), 0);
+    assert_eq!(
+        ctx.client
+            .get_juror_stake(&juror, &ctx.category, &ctx.token.address)
+            .locked,
+        to_stroops(20)
+    );
+    assert_eq!(ctx.escrow_balance(), to_stroops(120));
+    assert_eq!(
+        ctx.client.get_dispute(&dispute_id).escrowed,
+        to_stroops(120)
+    );
+
+    let res = ctx.client.try_assign_dispute(
+        &poor_juror,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrInsufficientStake)));
+    assert_eq!(ctx.juror_stake(&poor_juror), to_stroops(5));
+
+    let res = ctx.client.try_assign_dispute(
+        &outsider,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrNotRegistered)));
+    assert_eq!(ctx.round(dispute_id).assigned_jurors.len(), 1);
+}
+
+#[test]
+fn test_execute_slashes_incoherent_juror_stakes() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+    assert_eq!(ctx.escrow_balance(), to_stroops(150));
+
+    // Three jurors side with the claimer, two with the defender.
+    let votes = [1u32, 1, 1, 2, 2];
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.commit(&juror, dispute_id, votes[i], i as u8);
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+    }
+
+    let treasury_before = ctx.balance(&ctx.treasury);
+    let outcome = ctx.client.execute(&dispute_id);
+    assert_eq!(outcome, RulingOutcome::Majority(1));
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(claimer.clone()));
+
+    // The rounding remainder of the three-way split goes to the treasury
+    // along with the protocol fee.
+    let total_slashed = to_stroops(50) + to_stroops(10) * 2;
+    let treasury_fee = total_slashed * 5 / 100;
+    let reward_each = (total_slashed - treasury_fee) / 3;
+    let dust = total_slashed - treasury_fee - reward_each * 3;
+    assert!(dust > 0);
+
+    assert_eq!(
+        ctx.balance(&ctx.treasury) - treasury_before,
+        treasury_fee + dust
+    );
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    for (i, juror) in jurors.iter().enumerate() {
+        if votes[i] == 1 {
+            assert_eq!(ctx.juror_stake(&juror), to_stroops(100) + reward_each);
+        } else {
+            assert_eq!(ctx.juror_stake(&juror), to_stroops(90));
+        }
+    }
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert_eq!(dispute.escrowed, 0);
+    assert_eq!(ctx.escrow_balance(), 0);
+}
@@ -0,0 +1,174 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::events::{
+    CategoryAdded, CategoryRemoved, DisputeCreated, DisputePaid, JurorAssigned, PhaseChanged,
+    RoundExecuted, VoteCommitted, VoteRevealed,
+};
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::types::{DisputeStatus, RulingOutcome};
+use crate::xlm::to_stroops;
+use soroban_sdk::{
+    testutils::Events, vec, Address, Event, Symbol, TryFromVal, TryIntoVal, Val, Vec,
+};
+
+/// Returns the topics and data of every event the contract published under
+/// `topic` during the last call.
+fn events(ctx: &TestContext, topic: &str) -> Vec<(Vec<Val>, Val)> {
+    let topic = Symbol::new(&ctx.env, topic);
+    let mut found = Vec::new(&ctx.env);
+    for (contract, topics, data) in ctx.env.events().all().iter() {
+        if contract != ctx.client.address {
+            continue;
+        }
+        let first = topics
+            .get(0)
+            .and_then(|t| Symbol::try_from_val(&ctx.env, &t).ok());
+        if first.as_ref() == Some(&topic) {
+            found.push_back((topics, data));
+        }
+    }
+    found
+}
+
+/// Checks that `expected` is the only event published under its topic during
+/// the last call.
+fn assert_single(ctx: &TestContext, topic: &str, expected: impl Event) {
+    assert_eq!(
+        events(ctx, topic),
+        vec![
+            &ctx.env,
+            (expected.topics(&ctx.env), expected.data(&ctx.env))
+        ]
+    );
+}
+
+fn assert_phase(ctx: &TestContext, dispute_id: u64, status: DisputeStatus) {
+    let expected = PhaseChanged {
+        dispute_id,
+        round: 0,
+        status,
+    };
+    assert_single(ctx, "PHASE", expected);
+}
+
+/// Sums the `PAYOUT` events that went to `to`.
+fn paid_to(ctx: &TestContext, payouts: &Vec<(Vec<Val>, Val)>, to: &Address) -> i128 {
+    let mut total = 0;
+    for (_, data) in payouts.iter() {
+        let (recipient, amount): (Address, i128) = data.try_into_val(&ctx.env).ok().unwrap();
+        if &recipient == to {
+            total += amount;
+        }
+    }
+    total
+}
+
+#[test]
+fn test_category_events() {
+    let ctx = setup();
+    let name = Symbol::new(&ctx.env, "Art");
+
+    ctx.client.add_category(&name);
+    assert_single(&ctx, "CAT_ADD", CategoryAdded { name: name.clone() });
+
+    ctx.client.remove_category(&name);
+    assert_single(&ctx, "CAT_REM", CategoryRemoved { name });
+}
+
+#[test]
+fn test_dispute_lifecycle_events() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    let created = DisputeCreated {
+        dispute_id,
+        claimer: claimer.clone(),
+        defender: defender.clone(),
+        category: ctx.category.clone(),
+        jurors_required: 5,
+    };
+    assert_single(&ctx, "CREATED", created);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    assert!(events(&ctx, "PHASE").is_empty());
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+    let paid = DisputePaid {
+        dispute_id,
+        payer: defender.clone(),
+        amount: to_stroops(50),
+    };
+    assert_single(&ctx, "PAID", paid);
+    assert_phase(&ctx, dispute_id, DisputeStatus::Commit);
+
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        let assigned = JurorAssigned {
+            dispute_id,
+            round: 0,
+            juror,
+            stake: to_stroops(10),
+        };
+        assert_single(&ctx, "ASSIGNED", assigned);
+    }
+
+    let votes = [1, 1, 1, 2, 2];
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.commit(&juror, dispute_id, votes[i], i as u8);
+        let committed = VoteCommitted {
+            dispute_id,
+            round: 0,
+            juror,
+        };
+        assert_single(&ctx, "COMMITTED", committed);
+    }
+    assert_phase(&ctx, dispute_id, DisputeStatus::Reveal);
+
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+        let revealed = VoteRevealed {
+            dispute_id,
+            round: 0,
+            juror,
+            vote: votes[i],
+        };
+        assert_single(&ctx, "REVEALED", revealed);
+    }
+
+    let claimer_before = ctx.balance(&claimer);
+    let treasury_before = ctx.balance(&ctx.treasury);
+    let stakes_before: std::vec::Vec<i128> = jurors.iter().map(|j| ctx.juror_stake(&j)).collect();
+
+    ctx.client.execute(&dispute_id);
+    let executed = RoundExecuted {
+        dispute_id,
+        round: 0,
+        outcome: RulingOutcome::Majority(1),
+    };
+    assert_single(&ctx, "EXECUTED", executed);
+    assert_phase(&ctx, dispute_id, DisputeStatus::Finished);
+    let payouts = events(&ctx, "PAYOUT");
+
+    // Every payout event matches what actually reached the recipient.
+    assert_eq!(
+        paid_to(&ctx, &payouts, &claimer),
+        ctx.balance(&claimer) - claimer_before
+    );
+    assert_eq!(
+        paid_to(&ctx, &payouts, &ctx.treasury),
+        ctx.balance(&ctx.treasury) - treasury_before
+    );
+    assert_eq!(paid_to(&ctx, &payouts, &defender), 0);
+    for (i, juror) in jurors.iter().enumerate() {
+        let credited = ctx.juror_stake(&juror) - stakes_before[i];
+        assert_eq!(paid_to(&ctx, &payouts, &juror), credited);
+    }
+    assert!(paid_to(&ctx, &payouts, &claimer) > 0);
+}
@@ -0,0 +1,177 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::events::EvidenceSubmitted;
+use crate::tests::setup::{generate_jurors, setup, TestContext, COMMIT_SECONDS, PAY_SECONDS};
+use crate::types::{DisputeStatus, RulingOutcome, MAX_EVIDENCE};
+use crate::xlm::to_stroops;
+use soroban_sdk::{
+    testutils::{Address as _, Events},
+    vec, Address, BytesN, Event,
+};
+
+const EVIDENCE_SECONDS: u64 = 5400;
+
+/// Creates and funds a five-juror dispute with an evidence phase.
+fn evidence_dispute(ctx: &TestContext) -> (u64, Address, Address) {
+    ctx.client.set_evidence_limits(&0, &86_400);
+
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    params.limits.evidence_seconds = EVIDENCE_SECONDS;
+    let dispute_id = ctx.client.create_dispute(&params);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+    (dispute_id, claimer, defender)
+}
+
+fn hash(ctx: &TestContext, seed: u8) -> BytesN<32> {
+    BytesN::from_array(&ctx.env, &[seed; 32])
+}
+
+#[test]
+fn test_disputes_skip_evidence_by_default() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Commit);
+    let res = ctx
+        .client
+        .try_submit_evidence(&claimer, &dispute_id, &hash(&ctx, 1));
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceClosed)));
+
+    // The evidence window must be allowed by the config.
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    params.limits.evidence_seconds = EVIDENCE_SECONDS;
+    let res = ctx.client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidDeadline)));
+}
+
+#[test]
+fn test_evidence_window_must_fit_between_pay_and_commit() {
+    let ctx = setup();
+    ctx.client.set_evidence_limits(&0, &86_400);
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+
+    for seconds in [PAY_SECONDS - 1, COMMIT_SECONDS] {
+        let mut params =
+            ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+        params.limits.evidence_seconds = seconds;
+        let res = ctx.client.try_create_dispute(&params);
+        assert_eq!(res, Err(Ok(ContractError::ErrInvalidDeadline)));
+    }
+
+    let res = ctx.client.try_set_evidence_limits(&100, &50);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidDeadline)));
+}
+
+#[test]
+fn test_parties_build_evidence_log() {
+    let ctx = setup();
+    let (dispute_id, claimer, defender) = evidence_dispute(&ctx);
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Evidence);
+
+    ctx.client
+        .submit_evidence(&claimer, &dispute_id, &hash(&ctx, 1));
+    let expected = EvidenceSubmitted {
+        dispute_id,
+        submitter: claimer.clone(),
+        evidence_hash: hash(&ctx, 1),
+    };
+    let events = ctx.env.events().all();
+    assert_eq!(
+        events.slice(events.len() - 1..),
+        vec![
+            &ctx.env,
+            (
+                ctx.client.address.clone(),
+                expected.topics(&ctx.env),
+                expected.data(&ctx.env),
+            ),
+        ]
+    );
+
+    ctx.jump(60);
+    ctx.client
+        .submit_evidence(&defender, &dispute_id, &hash(&ctx, 2));
+
+    let log = ctx.client.get_evidence(&dispute_id);
+    assert_eq!(log.len(), 2);
+    let first = log.get(0).unwrap();
+    assert_eq!(first.submitter, claimer);
+    assert_eq!(first.evidence_hash, hash(&ctx, 1));
+    let second = log.get(1).unwrap();
+    assert_eq!(second.submitter, defender);
+    assert_eq!(second.submitted_at, first.submitted_at + 60);
+
+    let outsider = Address::generate(&ctx.env);
+    let res = ctx
+        .client
+        .try_submit_evidence(&outsider, &dispute_id, &hash(&ctx, 3));
+    assert_eq!(res, Err(Ok(ContractError::ErrUnauthorized)));
+
+    ctx.jump(EVIDENCE_SECONDS);
+    let res = ctx
+        .client
+        .try_submit_evidence(&claimer, &dispute_id, &hash(&ctx, 3));
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceClosed)));
+    assert_eq!(ctx.client.get_evidence(&dispute_id).len(), 2);
+}
+
+#[test]
+fn test_commits_wait_for_evidence_phase() {
+    let ctx = setup();
+    let (dispute_id, _, _) = evidence_dispute(&ctx);
+    let jurors = generate_jurors(&ctx, 5, to_stroops(100));
+
+    // Jurors can take their seats while evidence comes in.
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+    assert_eq!(ctx.round(dispute_id).assigned_jurors, jurors);
+
+    let juror = jurors.get(0).unwrap();
+    let res = ctx
+        .client
+        .try_commit_vote(&juror, &dispute_id, &hash(&ctx, 9));
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceOpen)));
+
+    ctx.jump(EVIDENCE_SECONDS + 1);
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.commit(&juror, dispute_id, 1, i as u8);
+    }
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Reveal);
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, 1, i as u8);
+    }
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Majority(1));
+}
+
+#[test]
+fn test_evidence_log_is_bounded() {
+    let ctx = setup();
+    let (dispute_id, claimer, _) = evidence_dispute(&ctx);
+
+    for i in 0..MAX_EVIDENCE {
+        ctx.client
+            .submit_evidence(&claimer, &dispute_id, &hash(&ctx, i as u8));
+    }
+    let res = ctx
+        .client
+        .try_submit_evidence(&claimer, &dispute_id, &hash(&ctx, 255));
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceFull)));
+}
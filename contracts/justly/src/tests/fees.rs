@@ -0,0 +1,170 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::storage;
+use crate::tests::setup::{config_limits, generate_jurors, setup, TestContext, PAY_SECONDS};
+use crate::types::{CategorySettings, MAX_BPS};
+use crate::xlm::to_stroops;
+use crate::Justly;
+use soroban_sdk::{testutils::Address as _, Address, Env, Vec};
+
+/// Creates and funds a five-juror dispute, both parties depositing 50, then
+/// seats five jurors who commit and reveal `votes`.
+fn decided_dispute(ctx: &TestContext, votes: &[u32; 5]) -> (u64, Address, Vec<Address>) {
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        ctx.commit(&juror, dispute_id, votes[i], i as u8);
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+    }
+    (dispute_id, claimer, jurors)
+}
+
+#[test]
+fn test_protocol_fee_goes_to_treasury() {
+    let ctx = setup();
+    let treasury = ctx.account();
+    ctx.client.set_treasury(&treasury);
+    ctx.client.set_fee_bps(&1_000);
+
+    let admin_before = ctx.balance(&ctx.admin);
+    let (dispute_id, _, _) = decided_dispute(&ctx, &[1; 5]);
+    ctx.client.execute(&dispute_id);
+
+    // 10% of the defender's deposit.
+    assert_eq!(ctx.balance(&treasury), to_stroops(5));
+    assert_eq!(ctx.balance(&ctx.admin), admin_before);
+}
+
+#[test]
+fn test_category_fee_overrides_default() {
+    let ctx = setup();
+    let treasury = ctx.account();
+    ctx.client.set_treasury(&treasury);
+    ctx.client.set_category_settings(
+        &ctx.category,
+        &CategorySettings {
+            fee_bps: Some(0),
+            ..Default::default()
+        },
+    );
+
+    let (dispute_id, claimer, jurors) = decided_dispute(&ctx, &[1; 5]);
+    ctx.client.execute(&dispute_id);
+
+    assert_eq!(ctx.balance(&treasury), 0);
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.juror_stake(&jurors.get(0).unwrap()), to_stroops(110));
+}
+
+#[test]
+fn test_arbitration_fee_pays_coherent_jurors() {
+    let ctx = setup();
+    let treasury = ctx.account();
+    ctx.client.set_treasury(&treasury);
+    ctx.client.set_fee_bps(&0);
+    ctx.client
+        .set_arbitration_fee(&ctx.token.address, &to_stroops(3));
+
+    // A refusal leaves nothing slashed from the parties and the four
+    // coherent jurors share only the arbitration fees and the dissenting
+    // stake.
+    let (dispute_id, claimer, jurors) = decided_dispute(&ctx, &[0, 0, 0, 0, 1]);
+    assert_eq!(ctx.balance(&claimer), to_stroops(947));
+    ctx.client.execute(&dispute_id);
+
+    assert_eq!(ctx.balance(&claimer), to_stroops(997));
+    let each = (to_stroops(6) + to_stroops(10)) / 4;
+    assert_eq!(
+        ctx.juror_stake(&jurors.get(0).unwrap()),
+        to_stroops(100) + each
+    );
+    assert_eq!(ctx.juror_stake(&jurors.get(4).unwrap()), to_stroops(90));
+    assert_eq!(ctx.balance(&treasury), 0);
+    assert_eq!(ctx.escrow_balance(), 0);
+}
+
+#[test]
+fn test_arbitration_fee_is_refunded_with_deposit() {
+    let ctx = setup();
+    ctx.client
+        .set_arbitration_fee(&ctx.token.address, &to_stroops(3));
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    // Later changes don't touch disputes that already exist.
+    ctx.client
+        .set_arbitration_fee(&ctx.token.address, &to_stroops(7));
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(40));
+    assert_eq!(ctx.balance(&claimer), to_stroops(957));
+
+    ctx.jump(PAY_SECONDS + 1);
+    ctx.client.cancel_unfunded(&dispute_id);
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.escrow_balance(), 0);
+}
+
+#[test]
+fn test_fee_setters_are_bounded() {
+    let ctx = setup();
+
+    let res = ctx.client.try_set_fee_bps(&(MAX_BPS + 1));
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidBps)));
+
+    let res = ctx.client.try_set_arbitration_fee(&ctx.token.address, &-1);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidAmount)));
+
+    let res = ctx.client.try_set_category_settings(
+        &ctx.category,
+        &CategorySettings {
+            fee_bps: Some(MAX_BPS + 1),
+            ..Default::default()
+        },
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidBps)));
+
+    ctx.client.set_fee_bps(&MAX_BPS);
+    let cfg = ctx.env.as_contract(&ctx.client.address, || {
+        storage::get_config(&ctx.env).unwrap()
+    });
+    assert_eq!(cfg.fee_bps, MAX_BPS);
+}
+
+#[test]
+fn test_treasury_is_kept_apart_from_admin() {
+    let ctx = setup();
+    assert_eq!(
+        ctx.env.as_contract(&ctx.client.address, || {
+            storage::get_config(&ctx.env).unwrap().treasury
+        }),
+        ctx.treasury
+    );
+
+    let res = ctx.client.try_set_treasury(&ctx.admin);
+    assert_eq!(res, Err(Ok(ContractError::ErrTreasuryIsAdmin)));
+}
+
+#[test]
+#[should_panic(expected = "Error(Contract, #49)")]
+fn test_constructor_rejects_admin_as_treasury() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    let (min_limits, max_limits) = config_limits();
+    env.register(Justly, (admin.clone(), admin, min_limits, max_limits));
+}
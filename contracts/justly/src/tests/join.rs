@@ -0,0 +1,101 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, COMMIT_SECONDS};
+use crate::types::DisputeStatus;
+use crate::xlm::to_stroops;
+use soroban_sdk::Vec;
+
+#[test]
+fn test_join_fills_private_panel() {
+    let ctx = setup();
+    let panel = generate_jurors(&ctx, 5, to_stroops(100));
+    let first = ctx
+        .funded_dispute(to_stroops(50), |params| {
+            params.allowed_jurors = Some(Vec::new(&ctx.env))
+        })
+        .0;
+    let second = ctx
+        .funded_dispute(to_stroops(50), |params| {
+            params.allowed_jurors = Some(panel.clone())
+        })
+        .0;
+
+    // Private panels stay out of the queue and are filled by id.
+    for juror in panel.iter() {
+        ctx.client.join_dispute(&juror, &second, &to_stroops(10));
+    }
+
+    let round = ctx.round(second);
+    assert_eq!(round.assigned_jurors, panel);
+    assert!(ctx.round(first).assigned_jurors.is_empty());
+    assert!(ctx.client.get_dispute(&second).status == DisputeStatus::Commit);
+}
+
+#[test]
+fn test_join_applies_assignment_checks() {
+    let ctx = setup();
+    let panel = generate_jurors(&ctx, 5, to_stroops(100));
+    let dispute_id = ctx
+        .funded_dispute(to_stroops(50), |params| {
+            params.allowed_jurors = Some(panel.clone())
+        })
+        .0;
+    let juror = panel.get(0).unwrap();
+
+    let outsider = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let res = ctx
+        .client
+        .try_join_dispute(&outsider, &dispute_id, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrNotAllowedJuror)));
+
+    let res = ctx
+        .client
+        .try_join_dispute(&juror, &dispute_id, &to_stroops(101));
+    assert_eq!(res, Err(Ok(ContractError::ErrStakeOutOfRange)));
+
+    ctx.client
+        .join_dispute(&juror, &dispute_id, &to_stroops(10));
+    let res = ctx
+        .client
+        .try_join_dispute(&juror, &dispute_id, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrAlreadyJuror)));
+
+    for juror in panel.iter().skip(1) {
+        ctx.client
+            .join_dispute(&juror, &dispute_id, &to_stroops(10));
+    }
+    let late = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let res = ctx
+        .client
+        .try_join_dispute(&late, &dispute_id, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrDisputeFull)));
+}
+
+#[test]
+fn test_join_rejects_closed_dispute() {
+    let ctx = setup();
+    let panel = generate_jurors(&ctx, 5, to_stroops(100));
+    let dispute_id = ctx
+        .funded_dispute(to_stroops(50), |params| {
+            params.allowed_jurors = Some(panel.clone())
+        })
+        .0;
+    let juror = panel.get(0).unwrap();
+
+    ctx.jump(COMMIT_SECONDS + 1);
+    let res = ctx
+        .client
+        .try_join_dispute(&juror, &dispute_id, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrDeadlineReached)));
+
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let unfunded = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    let res = ctx
+        .client
+        .try_join_dispute(&juror, &unfunded, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrNotActive)));
+}
@@ -0,0 +1,23 @@
+mod appeal;
+mod auth;
+mod choices;
+mod claimable;
+mod commitment;
+mod conservation;
+mod draw;
+mod escrow;
+mod events;
+mod evidence;
+mod fees;
+mod join;
+mod penalty;
+mod proof;
+mod queue;
+mod refund;
+mod registry;
+mod setup;
+mod split;
+mod staffing;
+mod storage;
+mod tie;
+mod tokens;
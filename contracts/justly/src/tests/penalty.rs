@@ -0,0 +1,136 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext, COMMIT_SECONDS, REVEAL_SECONDS};
+use crate::types::{JurorOutcome, NonRevealPenalty, RulingOutcome};
+use crate::xlm::to_stroops;
+use soroban_sdk::{vec, Address, Vec};
+
+/// Runs a dispute where two jurors vote for the claimer, one for the
+/// defender, one commits but never reveals and one never commits.
+fn run_dispute_with_absentees(ctx: &TestContext) -> (u64, Vec<Address>) {
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+
+    let votes = [1u32, 1, 2, 1];
+    for (i, vote) in votes.iter().enumerate() {
+        ctx.commit(&jurors.get(i as u32).unwrap(), dispute_id, *vote, i as u8);
+    }
+    ctx.jump(COMMIT_SECONDS + 1);
+    for (i, vote) in votes.iter().enumerate().take(3) {
+        ctx.reveal(&jurors.get(i as u32).unwrap(), dispute_id, *vote, i as u8);
+    }
+    ctx.jump(REVEAL_SECONDS);
+
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Majority(1));
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(claimer));
+    (dispute_id, jurors)
+}
+
+#[test]
+fn test_non_reveal_full_slash_records_outcomes() {
+    let ctx = setup();
+    let (dispute_id, jurors) = run_dispute_with_absentees(&ctx);
+
+    let round = ctx.round(dispute_id);
+    assert_eq!(
+        round.juror_outcomes,
+        vec![
+            &ctx.env,
+            JurorOutcome::Coherent,
+            JurorOutcome::Coherent,
+            JurorOutcome::Incoherent,
+            JurorOutcome::NotRevealed,
+            JurorOutcome::NotCommitted,
+        ]
+    );
+
+    for i in 2..5 {
+        assert_eq!(ctx.juror_stake(&jurors.get(i).unwrap()), to_stroops(90));
+    }
+    assert!(ctx.juror_stake(&jurors.get(0).unwrap()) > to_stroops(100));
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_non_reveal_partial_slash_returns_remainder() {
+    let ctx = setup();
+    ctx.client
+        .set_non_reveal_penalty(&NonRevealPenalty::PartialSlash(2_500));
+    let (dispute_id, jurors) = run_dispute_with_absentees(&ctx);
+
+    // Voting against the majority still costs the whole stake.
+    assert_eq!(ctx.juror_stake(&jurors.get(2).unwrap()), to_stroops(90));
+    // Absent jurors only lose a quarter of theirs.
+    assert_eq!(
+        ctx.juror_stake(&jurors.get(3).unwrap()),
+        to_stroops(97) + to_stroops(1) / 2
+    );
+    assert_eq!(
+        ctx.juror_stake(&jurors.get(4).unwrap()),
+        to_stroops(97) + to_stroops(1) / 2
+    );
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_non_reveal_cooldown_bans_juror() {
+    let ctx = setup();
+    ctx.client
+        .set_non_reveal_penalty(&NonRevealPenalty::Cooldown(86_400));
+    let (_, jurors) = run_dispute_with_absentees(&ctx);
+
+    let absent = jurors.get(3).unwrap();
+    assert_eq!(ctx.juror_stake(&absent), to_stroops(100));
+    assert_eq!(ctx.juror_stake(&jurors.get(4).unwrap()), to_stroops(100));
+    assert_eq!(ctx.juror_stake(&jurors.get(2).unwrap()), to_stroops(90));
+
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    let res =
+        ctx.client
+            .try_assign_dispute(&absent, &ctx.category, &ctx.token.address, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrJurorBanned)));
+
+    // The incoherent juror is not banned.
+    ctx.client.assign_dispute(
+        &jurors.get(2).unwrap(),
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+}
+
+#[test]
+fn test_set_non_reveal_penalty_validates_bps() {
+    let ctx = setup();
+    let res = ctx
+        .client
+        .try_set_non_reveal_penalty(&NonRevealPenalty::PartialSlash(10_001));
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidPenalty)));
+}
@@ -0,0 +1,174 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::reveal_public_inputs;
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::types::CategorySettings;
+use crate::xlm::to_stroops;
+use soroban_sdk::{contract, contractimpl, symbol_short, Address, Bytes, BytesN, Env, Symbol, Vec};
+
+const ACCEPT_KEY: Symbol = symbol_short!("ACCEPT");
+
+/// Stand-in for the UltraHonk verifier that accepts or rejects every proof
+/// depending on a flag.
+#[contract]
+pub struct MockVerifier;
+
+#[contractimpl]
+impl MockVerifier {
+    pub fn set_accept(env: Env, accept: bool) {
+        env.storage().instance().set(&ACCEPT_KEY, &accept);
+    }
+
+    pub fn verify_proof(env: Env, _vk_json: Bytes, proof_blob: Bytes) -> BytesN<32> {
+        if !env.storage().instance().get(&ACCEPT_KEY).unwrap_or(false) {
+            panic!("proof rejected");
+        }
+        env.crypto().sha256(&proof_blob).to_bytes()
+    }
+}
+
+/// Registers a mock verifier and turns proof checks on for the test category.
+fn enable_proofs<'a>(ctx: &TestContext<'a>, accept: bool) -> MockVerifierClient<'a> {
+    let id = ctx.env.register(MockVerifier, ());
+    let verifier = MockVerifierClient::new(&ctx.env, &id);
+    verifier.set_accept(&accept);
+
+    ctx.client.set_verifier(&id);
+    ctx.client.set_category_settings(
+        &ctx.category,
+        &CategorySettings {
+            verify_proofs: true,
+            ..Default::default()
+        },
+    );
+    verifier
+}
+
+/// Runs a dispute up to the reveal phase with every juror voting 1.
+fn committed_dispute(ctx: &TestContext) -> (u64, Vec<Address>) {
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        ctx.commit(&juror, dispute_id, 1, i as u8);
+    }
+    (dispute_id, jurors)
+}
+
+/// Builds a proof blob whose public inputs are bound to `juror` revealing in
+/// `dispute_id`.
+fn proof_blob(ctx: &TestContext, dispute_id: u64, juror: &Address, salt_seed: u8) -> Bytes {
+    let salt = BytesN::from_array(&ctx.env, &[salt_seed; 32]);
+    let commitment = ctx
+        .client
+        .compute_commitment_view(&dispute_id, &0, juror, &1, &salt);
+
+    let mut blob = Bytes::from_array(&ctx.env, &[0, 0, 0, 4]);
+    blob.append(&reveal_public_inputs(
+        &ctx.env,
+        dispute_id,
+        juror,
+        &commitment,
+    ));
+    blob.extend_from_array(&[7u8; 32]);
+    blob
+}
+
+#[test]
+fn test_reveal_accepts_bound_proof() {
+    let ctx = setup();
+    let justification = Bytes::from_array(&ctx.env, b"{}");
+    enable_proofs(&ctx, true);
+    let (dispute_id, jurors) = committed_dispute(&ctx);
+
+    for (i, juror) in jurors.iter().enumerate() {
+        let blob = proof_blob(&ctx, dispute_id, &juror, i as u8);
+        assert_eq!(
+            ctx.try_reveal(&juror, dispute_id, 1, i as u8, &justification, &blob),
+            Ok(())
+        );
+    }
+    assert_eq!(ctx.round(dispute_id).revealed_votes.get(0), Some(Some(1)));
+}
+
+#[test]
+fn test_reveal_rejected_by_verifier() {
+    let ctx = setup();
+    let justification = Bytes::from_array(&ctx.env, b"{}");
+    let verifier = enable_proofs(&ctx, false);
+    let (dispute_id, jurors) = committed_dispute(&ctx);
+    let juror = jurors.get(0).unwrap();
+    let blob = proof_blob(&ctx, dispute_id, &juror, 0);
+
+    assert_eq!(
+        ctx.try_reveal(&juror, dispute_id, 1, 0, &justification, &blob),
+        Err(ContractError::ErrInvalidProof)
+    );
+
+    verifier.set_accept(&true);
+    assert_eq!(
+        ctx.try_reveal(&juror, dispute_id, 1, 0, &justification, &blob),
+        Ok(())
+    );
+}
+
+#[test]
+fn test_reveal_rejects_proof_bound_elsewhere() {
+    let ctx = setup();
+    let justification = Bytes::from_array(&ctx.env, b"{}");
+    enable_proofs(&ctx, true);
+    let (dispute_id, jurors) = committed_dispute(&ctx);
+    let juror = jurors.get(0).unwrap();
+    let other = jurors.get(1).unwrap();
+    let claimer = ctx.funded_account(0);
+    let defender = ctx.funded_account(0);
+    let other_dispute = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    // Another juror's proof, a proof for another dispute, and a blob too
+    // short to hold the public inputs are all refused before the verifier
+    // is even called.
+    let cases = [
+        proof_blob(&ctx, dispute_id, &other, 0),
+        proof_blob(&ctx, other_dispute, &juror, 0),
+        proof_blob(&ctx, dispute_id, &juror, 1),
+        Bytes::from_array(&ctx.env, &[0, 0, 0, 4]),
+    ];
+    for blob in cases.iter() {
+        assert_eq!(
+            ctx.try_reveal(&juror, dispute_id, 1, 0, &justification, blob),
+            Err(ContractError::ErrInvalidProof)
+        );
+    }
+}
+
+#[test]
+fn test_reveal_requires_configured_verifier() {
+    let ctx = setup();
+    let justification = Bytes::from_array(&ctx.env, b"{}");
+    ctx.client.set_category_settings(
+        &ctx.category,
+        &CategorySettings {
+            verify_proofs: true,
+            ..Default::default()
+        },
+    );
+    let (dispute_id, jurors) = committed_dispute(&ctx);
+    let juror = jurors.get(0).unwrap();
+    let blob = proof_blob(&ctx, dispute_id, &juror, 0);
+
+    assert_eq!(
+        ctx.try_reveal(&juror, dispute_id, 1, 0, &justification, &blob),
+        Err(ContractError::ErrVerifierMissing)
+    );
+}
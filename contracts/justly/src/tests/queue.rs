@@ -0,0 +1,239 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::storage;
+use crate::tests::setup::{config_limits, generate_jurors, setup, TestContext};
+use crate::types::{DataKey, Dispute, QUEUE_SCAN_LIMIT};
+use crate::xlm::to_stroops;
+use crate::{Justly, JustlyClient};
+use soroban_sdk::testutils::EnvTestConfig;
+use soroban_sdk::xdr::{LedgerEntryData, LedgerKey, LedgerKeyContractData, ScVal};
+use soroban_sdk::{Address, Env, IntoVal, TryFromVal, Val, Vec};
+
+fn queue_bounds(ctx: &TestContext) -> (u64, u64) {
+    ctx.env.as_contract(&ctx.client.address, || {
+        (
+            storage::get_queue_head(&ctx.env, &ctx.category, &ctx.token.address),
+            storage::get_queue_tail(&ctx.env, &ctx.category, &ctx.token.address),
+        )
+    })
+}
+
+/// Moves an address over to another `Env`.
+fn address_in(env: &Env, address: &Address) -> Address {
+    Address::try_from_val(env, &ScVal::from(address)).unwrap()
+}
+
+/// Queues `queued` funded disputes and returns the entries read and the
+/// instructions used by one assignment. The first dispute goes through the
+/// contract and the rest are copies of its records written into a ledger
+/// snapshot, which a fresh `Env` only loads from as entries are touched.
+fn assign_cost(queued: u64) -> (u32, i64) {
+    let ctx = setup();
+    let first = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let juror = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let (dispute, round) = ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env.storage().persistent().set(
+            &DataKey::QueueTail(ctx.category.clone(), ctx.token.address.clone()),
+            &queued,
+        );
+        (
+            storage::get_dispute(&ctx.env, first).unwrap(),
+            storage::get_round(&ctx.env, first, 0).unwrap(),
+        )
+    });
+
+    ctx.env.cost_estimate().budget().reset_unlimited();
+    let mut snapshot = ctx.env.to_ledger_snapshot();
+    // The fresh `Env` hands out the same auth nonces again.
+    snapshot.ledger_entries.retain(|(key, _)| match &**key {
+        LedgerKey::ContractData(data) => !matches!(data.key, ScVal::LedgerKeyNonce(_)),
+        _ => true,
+    });
+    let to_scval = |val: Val| ScVal::try_from_val(&ctx.env, &val).unwrap();
+    let dispute_key = to_scval(DataKey::Dispute(first).into_val(&ctx.env));
+    let (template, live_until) = snapshot
+        .ledger_entries
+        .iter()
+        .find_map(|(_, (entry, live_until))| match &entry.data {
+            LedgerEntryData::ContractData(data) if data.key == dispute_key => {
+                Some((entry.clone(), *live_until))
+            }
+            _ => None,
+        })
+        .unwrap();
+    let mut copy = |key: DataKey, val: Val| {
+        let mut entry = template.clone();
+        let LedgerEntryData::ContractData(data) = &mut entry.data else {
+            unreachable!()
+        };
+        data.key = to_scval(key.into_val(&ctx.env));
+        data.val = to_scval(val);
+        let key = LedgerKey::ContractData(LedgerKeyContractData {
+            contract: data.contract.clone(),
+            key: data.key.clone(),
+            durability: data.durability,
+        });
+        snapshot
+            .ledger_entries
+            .push((std::boxed::Box::new(key), (entry, live_until)));
+    };
+    for id in first + 1..first + queued {
+        let queued_dispute = Dispute {
+            id,
+            ..dispute.clone()
+        };
+        copy(DataKey::Dispute(id), queued_dispute.into_val(&ctx.env));
+        copy(DataKey::Round(id, 0), round.into_val(&ctx.env));
+        copy(
+            DataKey::QueueEntry(ctx.category.clone(), ctx.token.address.clone(), id - first),
+            (id, 0u32).into_val(&ctx.env),
+        );
+    }
+
+    let mut env = Env::from_ledger_snapshot(snapshot);
+    env.set_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    let contract = address_in(&env, &ctx.client.address);
+    let token = address_in(&env, &ctx.token.address);
+    let juror = address_in(&env, &juror);
+    // Registering the contract code runs the constructor again, which
+    // clears the categories and the dispute counter.
+    let (min_limits, max_limits) = config_limits();
+    let admin = address_in(&env, &ctx.admin);
+    let treasury = address_in(&env, &ctx.treasury);
+    env.register_at(&contract, Justly, (admin, treasury, min_limits, max_limits));
+    let client = JustlyClient::new(&env, &contract);
+    client.add_category(&ctx.category);
+    env.as_contract(&contract, || {
+        storage::set_dispute_counter(&env, first + queued - 1);
+        assert_eq!(storage::get_queue_head(&env, &ctx.category, &token), 0);
+        assert_eq!(storage::get_queue_tail(&env, &ctx.category, &token), queued);
+    });
+
+    env.cost_estimate().budget().reset_default();
+    let (assigned, _) = client.assign_dispute(&juror, &ctx.category, &token, &to_stroops(10));
+    assert_eq!(assigned, first);
+
+    let resources = env.cost_estimate().resources();
+    (
+        resources.disk_read_entries + resources.memory_read_entries,
+        resources.instructions,
+    )
+}
+
+#[test]
+fn test_funding_enqueues_and_filling_dequeues() {
+    let ctx = setup();
+    assert_eq!(queue_bounds(&ctx), (0, 0));
+
+    let first = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let second = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    assert_eq!(queue_bounds(&ctx), (0, 2));
+
+    let jurors = generate_jurors(&ctx, 6, to_stroops(100));
+    for juror in jurors.iter().take(5) {
+        let (assigned, _) =
+            ctx.client
+                .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        assert_eq!(assigned, first);
+    }
+    assert_eq!(queue_bounds(&ctx), (1, 2));
+
+    let (assigned, _) = ctx.client.assign_dispute(
+        &jurors.get(5).unwrap(),
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(assigned, second);
+}
+
+#[test]
+fn test_private_disputes_stay_out_of_queue() {
+    let ctx = setup();
+    for _ in 0..=QUEUE_SCAN_LIMIT {
+        ctx.funded_dispute(to_stroops(50), |params| {
+            params.allowed_jurors = Some(Vec::new(&ctx.env))
+        });
+    }
+    assert_eq!(queue_bounds(&ctx), (0, 0));
+
+    let open = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    let juror = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let (assigned, _) =
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    assert_eq!(assigned, open);
+}
+
+#[test]
+fn test_own_seats_count_towards_scan() {
+    let ctx = setup();
+    let mut disputes = std::vec::Vec::new();
+    for _ in 0..=QUEUE_SCAN_LIMIT {
+        disputes.push(ctx.funded_dispute(to_stroops(50), |_| {}).0);
+    }
+
+    let juror = generate_jurors(&ctx, 1, to_stroops(1_000)).get(0).unwrap();
+    for dispute_id in disputes.iter().take(QUEUE_SCAN_LIMIT as usize) {
+        let (assigned, _) =
+            ctx.client
+                .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        assert_eq!(assigned, *dispute_id);
+    }
+
+    // The last dispute is past the rounds the juror already sits in.
+    let res =
+        ctx.client
+            .try_assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrNoAvailableDisputes)));
+
+    let last = disputes[QUEUE_SCAN_LIMIT as usize];
+    let res = ctx.client.try_join_dispute(&juror, &last, &to_stroops(10));
+    assert_eq!(res, Ok(Ok(())));
+}
+
+#[test]
+fn test_reserved_rounds_move_to_tail() {
+    let ctx = setup();
+    ctx.client.set_accept_window(&600);
+    generate_jurors(&ctx, 5, to_stroops(100));
+    for _ in 0..3 {
+        ctx.funded_dispute(to_stroops(50), |_| {});
+    }
+    ctx.client.set_accept_window(&0);
+    let open = ctx.funded_dispute(to_stroops(50), |_| {}).0;
+    assert_eq!(queue_bounds(&ctx), (0, 4));
+
+    let juror = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let (assigned, _) =
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    assert_eq!(assigned, open);
+    assert_eq!(queue_bounds(&ctx), (3, 7));
+
+    let order = ctx.env.as_contract(&ctx.client.address, || {
+        let mut order = std::vec::Vec::new();
+        for index in 3..7 {
+            let entry =
+                storage::get_queue_entry(&ctx.env, &ctx.category, &ctx.token.address, index);
+            order.push(entry.unwrap().0);
+        }
+        order
+    });
+    assert_eq!(order, [open, 1, 2, 3]);
+}
+
+#[test]
+fn test_assign_cost_does_not_grow_with_queue_length() {
+    let (small_reads, small_cpu) = assign_cost(1_000);
+    let (large_reads, large_cpu) = assign_cost(10_000);
+
+    assert_eq!(small_reads, large_reads);
+    assert_eq!(small_cpu, large_cpu);
+}
@@ -0,0 +1,108 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{setup, PAY_SECONDS};
+use crate::types::{DisputeStatus, RulingOutcome, TimeLimits, DEFENDER_CHOICE};
+use crate::xlm::to_stroops;
+
+#[test]
+fn test_cancel_unfunded_refunds_paying_party() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    assert_eq!(ctx.escrow_balance(), to_stroops(50));
+
+    let res = ctx.client.try_cancel_unfunded(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    ctx.jump(PAY_SECONDS + 1);
+    let res = ctx
+        .client
+        .try_pay_dispute(&defender, &dispute_id, &to_stroops(50));
+    assert_eq!(res, Err(Ok(ContractError::ErrDeadlineReached)));
+
+    ctx.client.cancel_unfunded(&dispute_id);
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.balance(&defender), to_stroops(1_000));
+    assert_eq!(ctx.escrow_balance(), 0);
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Cancelled);
+    assert_eq!(dispute.outcome, RulingOutcome::Refunded);
+    assert_eq!(dispute.escrowed, 0);
+    assert_eq!(ctx.client.get_winner(&dispute_id), None);
+
+    let res = ctx.client.try_cancel_unfunded(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrAlreadyFinished)));
+}
+
+#[test]
+fn test_cancel_unfunded_rejects_funded_dispute() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    ctx.jump(PAY_SECONDS + 1);
+    let res = ctx.client.try_cancel_unfunded(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrAlreadyFinished)));
+    assert_eq!(ctx.escrow_balance(), to_stroops(100));
+}
+
+#[test]
+fn test_cancel_unfunded_awards_default_to_payer() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    params.limits = TimeLimits {
+        pay_seconds: PAY_SECONDS,
+        evidence_seconds: 0,
+        commit_seconds: PAY_SECONDS,
+        reveal_seconds: PAY_SECONDS,
+    };
+    params.default_to_payer = true;
+    let dispute_id = ctx.client.create_dispute(&params);
+
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(30));
+    ctx.jump(PAY_SECONDS + 1);
+    ctx.client.cancel_unfunded(&dispute_id);
+
+    assert_eq!(ctx.balance(&defender), to_stroops(1_000));
+    assert_eq!(ctx.escrow_balance(), 0);
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(
+        dispute.outcome,
+        RulingOutcome::DefaultRuling(DEFENDER_CHOICE)
+    );
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(defender));
+}
+
+#[test]
+fn test_cancel_unfunded_without_payments_cancels() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.jump(PAY_SECONDS + 1);
+    ctx.client.cancel_unfunded(&dispute_id);
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Cancelled);
+    assert!(dispute.winner.is_none());
+}
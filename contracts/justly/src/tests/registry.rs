@@ -0,0 +1,238 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::types::JurorStake;
+use crate::xlm::to_stroops;
+use crate::{registry, storage};
+use soroban_sdk::{Address, Vec};
+
+const COOLDOWN: u64 = 86_400;
+
+fn registered_juror(ctx: &TestContext, stake: i128) -> Address {
+    generate_jurors(ctx, 1, stake).get(0).unwrap()
+}
+
+#[test]
+fn test_register_and_top_up() {
+    let ctx = setup();
+    let juror = ctx.funded_account(to_stroops(500));
+
+    ctx.client
+        .register_juror(&juror, &ctx.category, &ctx.token.address, &to_stroops(300));
+    assert_eq!(ctx.balance(&juror), to_stroops(200));
+    assert_eq!(ctx.contract_balance(), to_stroops(300));
+
+    let res =
+        ctx.client
+            .try_register_juror(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrAlreadyRegistered)));
+
+    ctx.client
+        .top_up(&juror, &ctx.category, &ctx.token.address, &to_stroops(150));
+    assert_eq!(
+        ctx.client
+            .get_juror_stake(&juror, &ctx.category, &ctx.token.address),
+        JurorStake {
+            amount: to_stroops(450),
+            locked: 0,
+            unstaking: 0,
+            unstake_at: 0,
+        }
+    );
+
+    let stranger = ctx.funded_account(to_stroops(100));
+    let res = ctx.client.try_top_up(
+        &stranger,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrNotRegistered)));
+}
+
+#[test]
+fn test_unstake_waits_for_cooldown() {
+    let ctx = setup();
+    ctx.client.set_unstake_cooldown(&COOLDOWN);
+    let juror = registered_juror(&ctx, to_stroops(100));
+
+    let res =
+        ctx.client
+            .try_request_unstake(&juror, &ctx.category, &ctx.token.address, &to_stroops(101));
+    assert_eq!(res, Err(Ok(ContractError::ErrInsufficientStake)));
+
+    let now = ctx.env.ledger().timestamp();
+    let unlock_at =
+        ctx.client
+            .request_unstake(&juror, &ctx.category, &ctx.token.address, &to_stroops(40));
+    assert_eq!(unlock_at, now + COOLDOWN);
+
+    let res = ctx
+        .client
+        .try_withdraw(&juror, &ctx.category, &ctx.token.address);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    ctx.jump(COOLDOWN);
+    assert_eq!(
+        ctx.client
+            .withdraw(&juror, &ctx.category, &ctx.token.address),
+        to_stroops(40)
+    );
+    assert_eq!(ctx.balance(&juror), to_stroops(40));
+    assert_eq!(ctx.juror_stake(&juror), to_stroops(60));
+
+    let res = ctx
+        .client
+        .try_withdraw(&juror, &ctx.category, &ctx.token.address);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidAmount)));
+}
+
+#[test]
+fn test_withdrawing_everything_leaves_the_pool() {
+    let ctx = setup();
+    let juror = registered_juror(&ctx, to_stroops(100));
+
+    ctx.client
+        .request_unstake(&juror, &ctx.category, &ctx.token.address, &to_stroops(100));
+    ctx.client
+        .withdraw(&juror, &ctx.category, &ctx.token.address);
+
+    assert_eq!(ctx.balance(&juror), to_stroops(100));
+    let res = ctx
+        .client
+        .try_get_juror_stake(&juror, &ctx.category, &ctx.token.address);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotRegistered)));
+
+    // The juror can register again from scratch.
+    ctx.client
+        .register_juror(&juror, &ctx.category, &ctx.token.address, &to_stroops(50));
+}
+
+#[test]
+fn test_locked_stake_cannot_be_unstaked() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let juror = registered_juror(&ctx, to_stroops(100));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    ctx.client
+        .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(30));
+
+    let res =
+        ctx.client
+            .try_request_unstake(&juror, &ctx.category, &ctx.token.address, &to_stroops(80));
+    assert_eq!(res, Err(Ok(ContractError::ErrInsufficientStake)));
+
+    ctx.client
+        .request_unstake(&juror, &ctx.category, &ctx.token.address, &to_stroops(70));
+
+    // Stake waiting to be withdrawn is no longer available.
+    let res = ctx
+        .client
+        .try_request_unstake(&juror, &ctx.category, &ctx.token.address, &1);
+    assert_eq!(res, Err(Ok(ContractError::ErrInsufficientStake)));
+    assert_eq!(
+        ctx.client
+            .get_juror_stake(&juror, &ctx.category, &ctx.token.address)
+            .locked,
+        to_stroops(30)
+    );
+}
+
+#[test]
+fn test_slashed_out_juror_leaves_the_pool() {
+    let ctx = setup();
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+
+    let mut jurors = generate_jurors(&ctx, 4, to_stroops(100));
+    let outvoted = registered_juror(&ctx, to_stroops(10));
+    jurors.push_back(outvoted.clone());
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+    let votes = [1, 1, 1, 1, 2];
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.commit(&juror, dispute_id, votes[i], i as u8);
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+    }
+    ctx.client.execute(&dispute_id);
+
+    let res = ctx
+        .client
+        .try_get_juror_stake(&outvoted, &ctx.category, &ctx.token.address);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotRegistered)));
+    assert_eq!(pool_members(&ctx), jurors.slice(0..4));
+}
+
+/// Members of the test category's pool, in slot order.
+fn pool_members(ctx: &TestContext) -> Vec<Address> {
+    ctx.env.as_contract(&ctx.client.address, || {
+        let token = &ctx.token.address;
+        let mut members = Vec::new(&ctx.env);
+        for index in 0..storage::get_pool_size(&ctx.env, &ctx.category, token) {
+            members.push_back(
+                storage::get_pool_member(&ctx.env, &ctx.category, token, index).unwrap(),
+            );
+        }
+        members
+    })
+}
+
+#[test]
+fn test_leaving_juror_slot_is_taken_by_last() {
+    let ctx = setup();
+    let jurors = generate_jurors(&ctx, 4, to_stroops(100));
+    assert_eq!(pool_members(&ctx), jurors);
+
+    let leaving = jurors.get(1).unwrap();
+    ctx.client.request_unstake(
+        &leaving,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(100),
+    );
+    ctx.client
+        .withdraw(&leaving, &ctx.category, &ctx.token.address);
+
+    let mut expected = jurors.clone();
+    expected.set(1, jurors.get(3).unwrap());
+    expected.pop_back();
+    assert_eq!(pool_members(&ctx), expected);
+
+    // Leaving from the last slot only shrinks the pool.
+    let last = jurors.get(2).unwrap();
+    ctx.client
+        .request_unstake(&last, &ctx.category, &ctx.token.address, &to_stroops(100));
+    ctx.client
+        .withdraw(&last, &ctx.category, &ctx.token.address);
+    expected.pop_back();
+    assert_eq!(pool_members(&ctx), expected);
+
+    ctx.client
+        .register_juror(&leaving, &ctx.category, &ctx.token.address, &to_stroops(50));
+    expected.push_back(leaving);
+    assert_eq!(pool_members(&ctx), expected);
+
+    // The pool stake follows the members that are left.
+    let pool_stake = ctx.env.as_contract(&ctx.client.address, || {
+        registry::pool_stake(&ctx.env, &ctx.category, &ctx.token.address)
+    });
+    assert_eq!(pool_stake, to_stroops(250));
+}
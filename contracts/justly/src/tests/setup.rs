@@ -0,0 +1,304 @@
+#![cfg(test)]
+
+extern crate std;
+
+use core::cell::RefCell;
+
+use crate::error::ContractError;
+use crate::types::{CreateDisputeParams, Round, RulingMode, RulingOutcome, TimeLimits};
+use crate::xlm::to_stroops;
+use crate::{storage, xlm, Justly, JustlyClient};
+use soroban_sdk::{
+    testutils::{Address as _, Ledger, StellarAssetContract},
+    token::{StellarAssetClient, TokenClient},
+    Address, Bytes, BytesN, Env, Symbol, Vec,
+};
+
+pub const PAY_SECONDS: u64 = 3600;
+pub const COMMIT_SECONDS: u64 = 7200;
+pub const REVEAL_SECONDS: u64 = 10800;
+
+pub struct TestContext<'a> {
+    pub env: Env,
+    pub admin: Address,
+    pub treasury: Address,
+    pub client: JustlyClient<'a>,
+    pub token: TokenClient<'a>,
+    pub sac: StellarAssetContract,
+    pub category: Symbol,
+    /// Every account the test created, so escrow can be told apart from
+    /// claimable payouts.
+    accounts: RefCell<std::vec::Vec<Address>>,
+}
+
+/// Deadline bounds the contract is constructed with. Evidence is off.
+pub fn config_limits() -> (TimeLimits, TimeLimits) {
+    let min = TimeLimits {
+        pay_seconds: 60,
+        evidence_seconds: 0,
+        commit_seconds: 60,
+        reveal_seconds: 60,
+    };
+    let max = TimeLimits {
+        pay_seconds: 86_400,
+        evidence_seconds: 0,
+        commit_seconds: 86_400,
+        reveal_seconds: 86_400,
+    };
+    (min, max)
+}
+
+pub fn setup<'a>() -> TestContext<'a> {
+    let env = Env::default();
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let treasury = Address::generate(&env);
+    let (min_limits, max_limits) = config_limits();
+    let id = env.register(
+        Justly,
+        (admin.clone(), treasury.clone(), min_limits, max_limits),
+    );
+    let client = JustlyClient::new(&env, &id);
+
+    let sac = env.as_contract(&id, || xlm::register(&env, &admin));
+    let token = TokenClient::new(&env, &sac.address());
+
+    client.add_token(&token.address);
+
+    let category = Symbol::new(&env, "General");
+    client.add_category(&category);
+
+    TestContext {
+        env,
+        admin: admin.clone(),
+        treasury: treasury.clone(),
+        client,
+        token,
+        sac,
+        category,
+        accounts: RefCell::new(std::vec![admin, treasury]),
+    }
+}
+
+impl TestContext<'_> {
+    /// Generates an account whose claimable balance counts towards
+    /// `escrow_balance`.
+    pub fn account(&self) -> Address {
+        let account = Address::generate(&self.env);
+        self.accounts.borrow_mut().push(account.clone());
+        account
+    }
+
+    pub fn funded_account(&self, amount: i128) -> Address {
+        let account = self.account();
+        StellarAssetClient::new(&self.env, &self.token.address).mint(&account, &amount);
+        account
+    }
+
+    /// Tokens held by the account plus anything it can still withdraw from
+    /// the contract.
+    pub fn balance(&self, account: &Address) -> i128 {
+        self.token.balance(account) + self.client.get_claimable(account, &self.token.address)
+    }
+
+    pub fn contract_balance(&self) -> i128 {
+        self.token.balance(&self.client.address)
+    }
+
+    /// Tokens the contract holds for disputes, leaving out free stake in the
+    /// juror registry and payouts claimable by the test accounts.
+    pub fn escrow_balance(&self) -> i128 {
+        let registered = self.env.as_contract(&self.client.address, || {
+            let token = &self.token.address;
+            let mut total = 0;
+            for account in self.accounts.borrow().iter() {
+                total += storage::get_claimable(&self.env, account, token);
+            }
+            for index in 0..storage::get_pool_size(&self.env, &self.category, token) {
+                let juror =
+                    storage::get_pool_member(&self.env, &self.category, token, index).unwrap();
+                if let Some(record) =
+                    storage::get_juror_stake(&self.env, &self.category, token, &juror)
+                {
+                    total += record.amount;
+                }
+            }
+            total
+        });
+        self.contract_balance() - registered
+    }
+
+    /// Free registered stake of a juror in the test category.
+    pub fn juror_stake(&self, juror: &Address) -> i128 {
+        self.client
+            .get_juror_stake(juror, &self.category, &self.token.address)
+            .amount
+    }
+
+    /// Current round of a dispute.
+    pub fn round(&self, dispute_id: u64) -> Round {
+        let dispute = self.client.get_dispute(&dispute_id);
+        self.client.get_round(&dispute_id, &dispute.round)
+    }
+
+    /// Parameters for a two-choice dispute in the test category.
+    pub fn dispute_params(
+        &self,
+        claimer: &Address,
+        defender: &Address,
+        min_amount: i128,
+        max_amount: i128,
+        jurors_required: u32,
+    ) -> CreateDisputeParams {
+        CreateDisputeParams {
+            claimer: claimer.clone(),
+            defender: defender.clone(),
+            delegate: None,
+            meta_hash: BytesN::from_array(&self.env, &[1u8; 32]),
+            min_amount,
+            max_amount,
+            category: self.category.clone(),
+            token: self.token.address.clone(),
+            allowed_jurors: None,
+            jurors_required,
+            ruling_mode: RulingMode::Choice,
+            num_choices: 2,
+            limits: TimeLimits {
+                pay_seconds: PAY_SECONDS,
+                evidence_seconds: 0,
+                commit_seconds: COMMIT_SECONDS,
+                reveal_seconds: REVEAL_SECONDS,
+            },
+            default_to_payer: false,
+        }
+    }
+
+    pub fn create_dispute(
+        &self,
+        claimer: &Address,
+        defender: &Address,
+        min_amount: i128,
+        max_amount: i128,
+        jurors_required: u32,
+    ) -> u64 {
+        let params =
+            self.dispute_params(claimer, defender, min_amount, max_amount, jurors_required);
+        self.client.create_dispute(&params)
+    }
+
+    /// Creates a five-juror dispute between two new accounts holding 1,000
+    /// of its token, after `customize` adjusts the parameters, and has the
+    /// claimer pay 50 and the defender `defender_pays`. Returns the dispute,
+    /// the claimer and the defender.
+    pub fn funded_dispute(
+        &self,
+        defender_pays: i128,
+        customize: impl FnOnce(&mut CreateDisputeParams),
+    ) -> (u64, Address, Address) {
+        let claimer = self.account();
+        let defender = self.account();
+        let mut params =
+            self.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+        customize(&mut params);
+
+        let asset = StellarAssetClient::new(&self.env, &params.token);
+        asset.mint(&claimer, &to_stroops(1_000));
+        asset.mint(&defender, &to_stroops(1_000));
+        let dispute_id = self.client.create_dispute(&params);
+        self.client
+            .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+        self.client
+            .pay_dispute(&defender, &dispute_id, &defender_pays);
+        (dispute_id, claimer, defender)
+    }
+
+    pub fn commit(&self, juror: &Address, dispute_id: u64, vote: u32, salt_seed: u8) {
+        let salt = BytesN::from_array(&self.env, &[salt_seed; 32]);
+        let round = self.client.get_dispute(&dispute_id).round;
+        let commitment =
+            self.client
+                .compute_commitment_view(&dispute_id, &round, juror, &vote, &salt);
+        self.client.commit_vote(juror, &dispute_id, &commitment);
+    }
+
+    pub fn reveal(&self, juror: &Address, dispute_id: u64, vote: u32, salt_seed: u8) {
+        let salt = BytesN::from_array(&self.env, &[salt_seed; 32]);
+        self.client.reveal_vote(
+            juror,
+            &dispute_id,
+            &vote,
+            &salt,
+            &Bytes::new(&self.env),
+            &Bytes::new(&self.env),
+        );
+    }
+
+    /// Like `reveal` with a justification and proof attached, returning the
+    /// contract error instead of panicking.
+    pub fn try_reveal(
+        &self,
+        juror: &Address,
+        dispute_id: u64,
+        vote: u32,
+        salt_seed: u8,
+        justification: &Bytes,
+        proof: &Bytes,
+    ) -> Result<(), ContractError> {
+        let salt = BytesN::from_array(&self.env, &[salt_seed; 32]);
+        match self
+            .client
+            .try_reveal_vote(juror, &dispute_id, &vote, &salt, justification, proof)
+        {
+            Ok(_) => Ok(()),
+            Err(Ok(err)) => Err(err),
+            Err(Err(_)) => panic!("unexpected host error"),
+        }
+    }
+
+    /// Seats `jurors` in the current round and executes it once they voted.
+    /// `ballot` gives each juror's stake, vote and whether it reveals. Unless
+    /// everyone reveals, execution waits for the reveal deadline.
+    pub fn play_round(
+        &self,
+        dispute_id: u64,
+        jurors: &Vec<Address>,
+        ballot: impl FnMut(usize) -> (i128, u32, bool),
+    ) -> RulingOutcome {
+        let ballots: std::vec::Vec<_> = (0..jurors.len() as usize).map(ballot).collect();
+        for (i, juror) in jurors.iter().enumerate() {
+            let (stake, vote, _) = ballots[i];
+            self.client
+                .assign_dispute(&juror, &self.category, &self.token.address, &stake);
+            self.commit(&juror, dispute_id, vote, i as u8);
+        }
+        for (i, juror) in jurors.iter().enumerate() {
+            let (_, vote, reveals) = ballots[i];
+            if reveals {
+                self.reveal(&juror, dispute_id, vote, i as u8);
+            }
+        }
+        if ballots.iter().any(|(_, _, reveals)| !reveals) {
+            self.jump(REVEAL_SECONDS + 1);
+        }
+        self.client.execute(&dispute_id)
+    }
+
+    pub fn jump(&self, seconds: u64) {
+        self.env
+            .ledger()
+            .with_mut(|li| li.timestamp = li.timestamp.saturating_add(seconds));
+    }
+}
+
+/// Creates `count` jurors, each registering `stake` in the test category.
+pub fn generate_jurors(ctx: &TestContext, count: u32, stake: i128) -> Vec<Address> {
+    let mut jurors = Vec::new(&ctx.env);
+    for _ in 0..count {
+        let juror = ctx.funded_account(stake);
+        ctx.client
+            .register_juror(&juror, &ctx.category, &ctx.token.address, &stake);
+        jurors.push_back(juror);
+    }
+    jurors
+}
@@ -0,0 +1,139 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext, REVEAL_SECONDS};
+use crate::types::{DisputeStatus, JurorOutcome, RulingMode, RulingOutcome, MAX_BPS};
+use crate::xlm::to_stroops;
+use soroban_sdk::{Address, BytesN, Vec};
+
+/// Creates and funds a five-juror split dispute, both parties paying 50.
+fn funded_split_dispute(ctx: &TestContext) -> (u64, Address, Address) {
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    params.ruling_mode = RulingMode::Split;
+    params.num_choices = 0;
+    let dispute_id = ctx.client.create_dispute(&params);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(50));
+    (dispute_id, claimer, defender)
+}
+
+/// Seats five jurors who all commit to `votes`.
+fn seat_and_commit(ctx: &TestContext, dispute_id: u64, votes: &[u32; 5]) -> Vec<Address> {
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+        ctx.commit(&juror, dispute_id, votes[i], i as u8);
+    }
+    jurors
+}
+
+#[test]
+fn test_median_vote_splits_deposits() {
+    let ctx = setup();
+    ctx.client.set_split_tolerance(&1_000);
+    let (dispute_id, claimer, defender) = funded_split_dispute(&ctx);
+    let claimer_before = ctx.balance(&claimer);
+    let defender_before = ctx.balance(&defender);
+
+    let votes = [7_000, 6_000, 7_500, 2_000, 10_000];
+    let jurors = seat_and_commit(&ctx, dispute_id, &votes);
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+    }
+
+    let outcome = ctx.client.execute(&dispute_id);
+    assert_eq!(outcome, RulingOutcome::Split(7_000));
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(dispute.winner, None);
+    assert_eq!(ctx.balance(&claimer), claimer_before + to_stroops(70));
+    assert_eq!(ctx.balance(&defender), defender_before + to_stroops(30));
+
+    // Within 10% of the median is coherent, further out is slashed.
+    let round = ctx.round(dispute_id);
+    for (i, expected) in [
+        JurorOutcome::Coherent,
+        JurorOutcome::Coherent,
+        JurorOutcome::Coherent,
+        JurorOutcome::Incoherent,
+        JurorOutcome::Incoherent,
+    ]
+    .iter()
+    .enumerate()
+    {
+        assert_eq!(round.juror_outcomes.get(i as u32), Some(*expected));
+    }
+    assert!(ctx.juror_stake(&jurors.get(1).unwrap()) > to_stroops(100));
+    assert_eq!(ctx.juror_stake(&jurors.get(3).unwrap()), to_stroops(90));
+}
+
+#[test]
+fn test_even_reveal_count_takes_midpoint_of_middle_votes() {
+    let ctx = setup();
+    let (dispute_id, _, _) = funded_split_dispute(&ctx);
+
+    let votes = [1_000, 3_000, 5_000, 9_000, 0];
+    let jurors = seat_and_commit(&ctx, dispute_id, &votes);
+    for (i, juror) in jurors.iter().take(4).enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+    }
+    ctx.jump(REVEAL_SECONDS + 1);
+
+    let outcome = ctx.client.execute(&dispute_id);
+    assert_eq!(outcome, RulingOutcome::Split(4_000));
+
+    // Nobody voted exactly on the midpoint, so with no tolerance every
+    // revealed vote is incoherent.
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.juror_outcomes.get(0), Some(JurorOutcome::Incoherent));
+    assert_eq!(round.juror_outcomes.get(4), Some(JurorOutcome::NotRevealed));
+}
+
+#[test]
+fn test_either_party_can_appeal_a_split() {
+    let ctx = setup();
+    ctx.client.set_appeal_window(&3600);
+    let (dispute_id, claimer, defender) = funded_split_dispute(&ctx);
+
+    let votes = [5_000; 5];
+    let jurors = seat_and_commit(&ctx, dispute_id, &votes);
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, votes[i], i as u8);
+    }
+    ctx.client.execute(&dispute_id);
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Appealable);
+
+    assert_eq!(ctx.client.appeal(&claimer, &dispute_id), 1);
+    assert_eq!(ctx.round(dispute_id).appellant, Some(claimer));
+    assert_ne!(ctx.round(dispute_id).appellant, Some(defender));
+}
+
+#[test]
+fn test_split_votes_and_tolerance_are_bounded() {
+    let ctx = setup();
+    let res = ctx.client.try_set_split_tolerance(&(MAX_BPS + 1));
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidBps)));
+
+    let (dispute_id, _, _) = funded_split_dispute(&ctx);
+    let juror = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let salt = BytesN::from_array(&ctx.env, &[3; 32]);
+    let res =
+        ctx.client
+            .try_compute_commitment_view(&dispute_id, &0, &juror, &(MAX_BPS + 1), &salt);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidVote)));
+
+    // A full award to the claimer is a valid vote.
+    assert!(ctx
+        .client
+        .try_compute_commitment_view(&dispute_id, &0, &juror, &MAX_BPS, &salt)
+        .is_ok());
+}
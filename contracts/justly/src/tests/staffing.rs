@@ -0,0 +1,198 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext, COMMIT_SECONDS};
+use crate::types::{DisputeStatus, RulingOutcome, UnderstaffedPolicy};
+use crate::xlm::to_stroops;
+use soroban_sdk::{Address, Bytes, BytesN, Vec};
+
+fn assign_all(ctx: &TestContext, jurors: &Vec<Address>) {
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+}
+
+#[test]
+fn test_partial_panel_does_not_reach_reveal() {
+    let ctx = setup();
+    let (dispute_id, _, _) = ctx.funded_dispute(to_stroops(50), |_| {});
+    let jurors = generate_jurors(&ctx, 3, to_stroops(100));
+    assign_all(&ctx, &jurors);
+
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.commit(&juror, dispute_id, 1, i as u8);
+    }
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Commit);
+
+    let res = ctx.client.try_execute(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotActive)));
+
+    ctx.jump(COMMIT_SECONDS + 1);
+    let res = ctx.client.try_execute(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrUnderstaffed)));
+
+    let juror = jurors.get(0).unwrap();
+    let res = ctx.client.try_reveal_vote(
+        &juror,
+        &dispute_id,
+        &0,
+        &BytesN::from_array(&ctx.env, &[0u8; 32]),
+        &Bytes::new(&ctx.env),
+        &Bytes::new(&ctx.env),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrUnderstaffed)));
+
+    let late_juror = ctx.funded_account(to_stroops(100));
+    let res = ctx.client.try_assign_dispute(
+        &late_juror,
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrNoAvailableDisputes)));
+}
+
+#[test]
+fn test_resolve_understaffed_refunds_everyone() {
+    let ctx = setup();
+    let (dispute_id, claimer, defender) = ctx.funded_dispute(to_stroops(50), |_| {});
+    let jurors = generate_jurors(&ctx, 2, to_stroops(100));
+    assign_all(&ctx, &jurors);
+    assert_eq!(ctx.escrow_balance(), to_stroops(120));
+
+    let res = ctx.client.try_resolve_understaffed(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    ctx.jump(COMMIT_SECONDS + 1);
+    let applied = ctx.client.resolve_understaffed(&dispute_id);
+    assert_eq!(applied, UnderstaffedPolicy::Refund);
+
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.balance(&defender), to_stroops(1_000));
+    for juror in jurors.iter() {
+        assert_eq!(ctx.juror_stake(&juror), to_stroops(100));
+    }
+    assert_eq!(ctx.escrow_balance(), 0);
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Cancelled);
+    assert_eq!(dispute.escrowed, 0);
+
+    let res = ctx.client.try_resolve_understaffed(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotActive)));
+}
+
+#[test]
+fn test_resolve_understaffed_extends_commit_window() {
+    let ctx = setup();
+    ctx.client
+        .set_understaffed_policy(&UnderstaffedPolicy::ExtendCommit, &1, &3600);
+
+    let (dispute_id, claimer, _) = ctx.funded_dispute(to_stroops(50), |_| {});
+    let first = generate_jurors(&ctx, 3, to_stroops(100));
+    assign_all(&ctx, &first);
+    ctx.commit(&first.get(0).unwrap(), dispute_id, 1, 0);
+
+    ctx.jump(COMMIT_SECONDS + 1);
+    let before = ctx.round(dispute_id);
+    let applied = ctx.client.resolve_understaffed(&dispute_id);
+    assert_eq!(applied, UnderstaffedPolicy::ExtendCommit);
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    let round = ctx.round(dispute_id);
+    let now = ctx.env.ledger().timestamp();
+    assert!(dispute.status == DisputeStatus::Commit);
+    assert_eq!(round.commit_extensions, 1);
+    assert_eq!(round.deadline_commit_seconds, now + 3600);
+    assert_eq!(
+        round.deadline_reveal_seconds - round.deadline_commit_seconds,
+        before.deadline_reveal_seconds - before.deadline_commit_seconds
+    );
+    assert_eq!(round.assigned_jurors, first);
+
+    let second = generate_jurors(&ctx, 2, to_stroops(100));
+    assign_all(&ctx, &second);
+    let mut jurors = first.clone();
+    jurors.append(&second);
+    for (i, juror) in jurors.iter().enumerate().skip(1) {
+        ctx.commit(&juror, dispute_id, 1, i as u8);
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(&juror, dispute_id, 1, i as u8);
+    }
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Majority(1));
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(claimer));
+}
+
+#[test]
+fn test_resolve_understaffed_reopens_idle_seats() {
+    let ctx = setup();
+    ctx.client
+        .set_understaffed_policy(&UnderstaffedPolicy::Reopen, &1, &3600);
+
+    let (dispute_id, _, _) = ctx.funded_dispute(to_stroops(50), |_| {});
+    let jurors = generate_jurors(&ctx, 3, to_stroops(100));
+    assign_all(&ctx, &jurors);
+    ctx.commit(&jurors.get(0).unwrap(), dispute_id, 1, 0);
+
+    ctx.jump(COMMIT_SECONDS + 1);
+    let applied = ctx.client.resolve_understaffed(&dispute_id);
+    assert_eq!(applied, UnderstaffedPolicy::Reopen);
+
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.assigned_jurors.len(), 1);
+    assert_eq!(round.assigned_jurors.get(0), jurors.get(0));
+    assert_eq!(round.commitments.len(), 1);
+    assert_eq!(
+        ctx.client.get_dispute(&dispute_id).escrowed,
+        to_stroops(110)
+    );
+
+    assert_eq!(ctx.juror_stake(&jurors.get(0).unwrap()), to_stroops(90));
+    assert_eq!(ctx.juror_stake(&jurors.get(1).unwrap()), to_stroops(100));
+    assert_eq!(ctx.juror_stake(&jurors.get(2).unwrap()), to_stroops(100));
+
+    // The released seats are open again.
+    ctx.client.assign_dispute(
+        &jurors.get(1).unwrap(),
+        &ctx.category,
+        &ctx.token.address,
+        &to_stroops(10),
+    );
+    assert_eq!(ctx.round(dispute_id).assigned_jurors.len(), 2);
+}
+
+#[test]
+fn test_resolve_understaffed_refunds_after_max_extensions() {
+    let ctx = setup();
+    ctx.client
+        .set_understaffed_policy(&UnderstaffedPolicy::ExtendCommit, &1, &3600);
+
+    let (dispute_id, claimer, _) = ctx.funded_dispute(to_stroops(50), |_| {});
+
+    ctx.jump(COMMIT_SECONDS + 1);
+    assert_eq!(
+        ctx.client.resolve_understaffed(&dispute_id),
+        UnderstaffedPolicy::ExtendCommit
+    );
+
+    ctx.jump(3601);
+    assert_eq!(
+        ctx.client.resolve_understaffed(&dispute_id),
+        UnderstaffedPolicy::Refund
+    );
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert!(ctx.client.get_dispute(&dispute_id).status == DisputeStatus::Cancelled);
+}
+
+#[test]
+fn test_set_understaffed_policy_validates_extension() {
+    let ctx = setup();
+    let res = ctx
+        .client
+        .try_set_understaffed_policy(&UnderstaffedPolicy::ExtendCommit, &1, &0);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidDeadline)));
+}
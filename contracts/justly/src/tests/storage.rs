@@ -0,0 +1,103 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{setup, TestContext};
+use crate::types::DataKey;
+use crate::xlm::to_stroops;
+use justly_storage::{DAY_IN_LEDGERS, TTL_EXTEND_TO};
+use soroban_sdk::testutils::storage::{Instance, Persistent};
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::Address;
+
+fn instance_entries(ctx: &TestContext) -> u32 {
+    ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env.storage().instance().all().len()
+    })
+}
+
+fn instance_ttl(ctx: &TestContext) -> u32 {
+    ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env.storage().instance().get_ttl()
+    })
+}
+
+fn dispute_ttl(ctx: &TestContext, dispute_id: u64) -> u32 {
+    ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::Dispute(dispute_id))
+    })
+}
+
+#[test]
+fn test_thousands_of_disputes_do_not_grow_instance_storage() {
+    let ctx = setup();
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+
+    ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    let baseline = instance_entries(&ctx);
+
+    for _ in 0..2_000 {
+        ctx.env.cost_estimate().budget().reset_default();
+        ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    }
+
+    assert_eq!(instance_entries(&ctx), baseline);
+    assert_eq!(ctx.client.get_dispute(&2_001).id, 2_001);
+    assert_eq!(ctx.client.get_round(&2_001, &0).jurors_required, 5);
+}
+
+#[test]
+fn test_bump_dispute_ttl_extends_dispute_and_rounds() {
+    let ctx = setup();
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    assert_eq!(dispute_ttl(&ctx, dispute_id), TTL_EXTEND_TO);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.sequence_number += 2 * DAY_IN_LEDGERS);
+    assert_eq!(
+        dispute_ttl(&ctx, dispute_id),
+        TTL_EXTEND_TO - 2 * DAY_IN_LEDGERS
+    );
+
+    ctx.client.bump_dispute_ttl(&dispute_id);
+    assert_eq!(dispute_ttl(&ctx, dispute_id), TTL_EXTEND_TO);
+    assert_eq!(instance_ttl(&ctx), TTL_EXTEND_TO);
+    let round_ttl = ctx.env.as_contract(&ctx.client.address, || {
+        ctx.env
+            .storage()
+            .persistent()
+            .get_ttl(&DataKey::Round(dispute_id, 0))
+    });
+    assert_eq!(round_ttl, TTL_EXTEND_TO);
+
+    let res = ctx.client.try_bump_dispute_ttl(&42);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotFound)));
+}
+
+#[test]
+fn test_state_changes_extend_instance_ttl() {
+    let ctx = setup();
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.sequence_number += 2 * DAY_IN_LEDGERS);
+    assert_eq!(instance_ttl(&ctx), TTL_EXTEND_TO - 2 * DAY_IN_LEDGERS);
+    ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    assert_eq!(instance_ttl(&ctx), TTL_EXTEND_TO);
+
+    ctx.env
+        .ledger()
+        .with_mut(|li| li.sequence_number += 2 * DAY_IN_LEDGERS);
+    ctx.client.set_appeal_window(&3600);
+    assert_eq!(instance_ttl(&ctx), TTL_EXTEND_TO);
+}
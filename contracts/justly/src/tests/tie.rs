@@ -0,0 +1,142 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext, COMMIT_SECONDS, REVEAL_SECONDS};
+use crate::types::{
+    CategorySettings, DisputeStatus, JurorOutcome, RulingOutcome, TiePolicy, MAX_CHOICES,
+};
+use crate::xlm::to_stroops;
+use soroban_sdk::{Address, Vec};
+
+/// Funds a dispute, seats five jurors and has the first `votes.len()` of
+/// them commit and reveal. Returns once the reveal window has closed.
+fn run_dispute(ctx: &TestContext, votes: &[u32]) -> (u64, Address, Address, Vec<Address>) {
+    let claimer = ctx.funded_account(to_stroops(1_000));
+    let defender = ctx.funded_account(to_stroops(1_000));
+    let jurors = generate_jurors(ctx, 5, to_stroops(100));
+    let dispute_id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+
+    ctx.client
+        .pay_dispute(&claimer, &dispute_id, &to_stroops(50));
+    ctx.client
+        .pay_dispute(&defender, &dispute_id, &to_stroops(40));
+    for juror in jurors.iter() {
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    }
+
+    for (i, vote) in votes.iter().enumerate() {
+        ctx.commit(&jurors.get(i as u32).unwrap(), dispute_id, *vote, i as u8);
+    }
+    ctx.jump(COMMIT_SECONDS + 1);
+    for (i, vote) in votes.iter().enumerate() {
+        ctx.reveal(&jurors.get(i as u32).unwrap(), dispute_id, *vote, i as u8);
+    }
+    ctx.jump(REVEAL_SECONDS);
+
+    (dispute_id, claimer, defender, jurors)
+}
+
+#[test]
+fn test_tie_refunds_both_parties_by_default() {
+    let ctx = setup();
+    let (dispute_id, claimer, defender, jurors) = run_dispute(&ctx, &[1, 2, 1, 2]);
+
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Refunded);
+    assert_eq!(ctx.client.get_winner(&dispute_id), None);
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.balance(&defender), to_stroops(1_000));
+
+    // Revealing jurors keep their stake and split the absent juror's slash.
+    let treasury_fee = to_stroops(10) * 5 / 100;
+    let reward_each = (to_stroops(10) - treasury_fee) / 4;
+    for i in 0..4 {
+        assert_eq!(
+            ctx.juror_stake(&jurors.get(i).unwrap()),
+            to_stroops(100) + reward_each
+        );
+    }
+    assert_eq!(ctx.juror_stake(&jurors.get(4).unwrap()), to_stroops(90));
+
+    let dispute = ctx.client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Finished);
+    assert_eq!(dispute.outcome, RulingOutcome::Refunded);
+    assert_eq!(ctx.escrow_balance(), dispute.escrowed);
+}
+
+#[test]
+fn test_no_reveals_refunds_parties_and_slashes_jurors() {
+    let ctx = setup();
+    let (dispute_id, claimer, defender, jurors) = run_dispute(&ctx, &[]);
+
+    let treasury_before = ctx.balance(&ctx.treasury);
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Refunded);
+    assert_eq!(ctx.balance(&claimer), to_stroops(1_000));
+    assert_eq!(ctx.balance(&defender), to_stroops(1_000));
+
+    // Nobody earned the pool, so it all goes to the treasury.
+    assert_eq!(ctx.balance(&ctx.treasury) - treasury_before, to_stroops(50));
+    for juror in jurors.iter() {
+        assert_eq!(ctx.juror_stake(&juror), to_stroops(90));
+    }
+    assert_eq!(ctx.escrow_balance(), 0);
+}
+
+#[test]
+fn test_tie_applies_category_default_ruling() {
+    let ctx = setup();
+    ctx.client.set_tie_policy(&TiePolicy::DefaultRuling);
+    ctx.client.set_category_settings(
+        &ctx.category,
+        &CategorySettings {
+            default_ruling: Some(2),
+            ..Default::default()
+        },
+    );
+
+    let (dispute_id, _, defender, _) = run_dispute(&ctx, &[1, 2, 1, 2]);
+
+    assert_eq!(
+        ctx.client.execute(&dispute_id),
+        RulingOutcome::DefaultRuling(2)
+    );
+    assert_eq!(ctx.client.get_winner(&dispute_id), Some(defender));
+
+    let round = ctx.round(dispute_id);
+    assert_eq!(round.juror_outcomes.get(0), Some(JurorOutcome::Incoherent));
+    assert_eq!(round.juror_outcomes.get(1), Some(JurorOutcome::Coherent));
+    assert_eq!(
+        ctx.escrow_balance(),
+        ctx.client.get_dispute(&dispute_id).escrowed
+    );
+}
+
+#[test]
+fn test_default_ruling_policy_without_default_refunds() {
+    let ctx = setup();
+    ctx.client.set_tie_policy(&TiePolicy::DefaultRuling);
+
+    let (dispute_id, _, _, _) = run_dispute(&ctx, &[1, 2]);
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Refunded);
+}
+
+#[test]
+fn test_set_category_settings_validation() {
+    let ctx = setup();
+    let res = ctx.client.try_set_category_settings(
+        &ctx.category,
+        &CategorySettings {
+            default_ruling: Some(MAX_CHOICES + 1),
+            ..Default::default()
+        },
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidVote)));
+
+    let res = ctx.client.try_set_category_settings(
+        &soroban_sdk::Symbol::new(&ctx.env, "Unknown"),
+        &CategorySettings::default(),
+    );
+    assert_eq!(res, Err(Ok(ContractError::ErrCategoryNotFound)));
+}
@@ -0,0 +1,184 @@
+#![cfg(test)]
+
+extern crate std;
+
+use crate::error::ContractError;
+use crate::tests::setup::{generate_jurors, setup, TestContext};
+use crate::types::RulingOutcome;
+use crate::xlm::to_stroops;
+use soroban_sdk::{
+    testutils::Address as _,
+    token::{StellarAssetClient, TokenClient},
+    Address,
+};
+
+/// Registers a second asset and allowlists it.
+fn add_usdc<'a>(ctx: &TestContext<'a>) -> TokenClient<'a> {
+    let sac = ctx
+        .env
+        .register_stellar_asset_contract_v2(ctx.admin.clone());
+    ctx.client.add_token(&sac.address());
+    TokenClient::new(&ctx.env, &sac.address())
+}
+
+fn usdc_account(ctx: &TestContext, usdc: &TokenClient, amount: i128) -> Address {
+    let account = Address::generate(&ctx.env);
+    StellarAssetClient::new(&ctx.env, &usdc.address).mint(&account, &amount);
+    account
+}
+
+#[test]
+fn test_dispute_settles_in_its_own_token() {
+    let ctx = setup();
+    let usdc = add_usdc(&ctx);
+    let (dispute_id, claimer, defender) =
+        ctx.funded_dispute(to_stroops(50), |params| params.token = usdc.address.clone());
+    assert_eq!(usdc.balance(&ctx.client.address), to_stroops(100));
+    assert_eq!(ctx.contract_balance(), 0);
+
+    let mut jurors = std::vec::Vec::new();
+    for i in 0..5u8 {
+        let juror = usdc_account(&ctx, &usdc, to_stroops(100));
+        ctx.client
+            .register_juror(&juror, &ctx.category, &usdc.address, &to_stroops(100));
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &usdc.address, &to_stroops(10));
+        ctx.commit(&juror, dispute_id, 1, i);
+        jurors.push(juror);
+    }
+    for (i, juror) in jurors.iter().enumerate() {
+        ctx.reveal(juror, dispute_id, 1, i as u8);
+    }
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Majority(1));
+
+    // Everything is paid in USDC and nothing moved in the default token.
+    assert_eq!(
+        ctx.client.withdraw_claimable(&claimer, &usdc.address),
+        to_stroops(50)
+    );
+    assert_eq!(usdc.balance(&claimer), to_stroops(1_000));
+    assert_eq!(usdc.balance(&defender), to_stroops(950));
+    assert_eq!(ctx.client.get_claimable(&claimer, &ctx.token.address), 0);
+    assert!(
+        ctx.client
+            .get_juror_stake(&jurors[0], &ctx.category, &usdc.address)
+            .amount
+            > to_stroops(100)
+    );
+    let res = ctx
+        .client
+        .try_get_juror_stake(&jurors[0], &ctx.category, &ctx.token.address);
+    assert_eq!(res, Err(Ok(ContractError::ErrNotRegistered)));
+    assert_eq!(ctx.contract_balance(), 0);
+}
+
+#[test]
+fn test_jurors_are_matched_by_token() {
+    let ctx = setup();
+    let usdc = add_usdc(&ctx);
+    let xlm_dispute = {
+        let claimer = ctx.funded_account(to_stroops(1_000));
+        let defender = ctx.funded_account(to_stroops(1_000));
+        let id = ctx.create_dispute(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+        ctx.client.pay_dispute(&claimer, &id, &to_stroops(50));
+        ctx.client.pay_dispute(&defender, &id, &to_stroops(50));
+        id
+    };
+    let (usdc_dispute, _, _) =
+        ctx.funded_dispute(to_stroops(50), |params| params.token = usdc.address.clone());
+
+    let juror = usdc_account(&ctx, &usdc, to_stroops(100));
+    ctx.client
+        .register_juror(&juror, &ctx.category, &usdc.address, &to_stroops(100));
+    let (assigned, _) =
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &usdc.address, &to_stroops(10));
+    assert_eq!(assigned, usdc_dispute);
+
+    let juror = generate_jurors(&ctx, 1, to_stroops(100)).get(0).unwrap();
+    let (assigned, _) =
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &ctx.token.address, &to_stroops(10));
+    assert_eq!(assigned, xlm_dispute);
+}
+
+#[test]
+fn test_token_allowlist_is_enforced() {
+    let ctx = setup();
+    let other = ctx
+        .env
+        .register_stellar_asset_contract_v2(ctx.admin.clone())
+        .address();
+    let claimer = Address::generate(&ctx.env);
+    let defender = Address::generate(&ctx.env);
+    let mut params = ctx.dispute_params(&claimer, &defender, to_stroops(10), to_stroops(100), 5);
+    params.token = other.clone();
+
+    let res = ctx.client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrTokenNotAllowed)));
+    let res = ctx
+        .client
+        .try_register_juror(&claimer, &ctx.category, &other, &to_stroops(10));
+    assert_eq!(res, Err(Ok(ContractError::ErrTokenNotAllowed)));
+    let res = ctx.client.try_set_arbitration_fee(&other, &1);
+    assert_eq!(res, Err(Ok(ContractError::ErrTokenNotAllowed)));
+
+    ctx.client.add_token(&other);
+    assert!(ctx.client.is_token_allowed(&other));
+    assert_eq!(
+        ctx.client.try_add_token(&other),
+        Err(Ok(ContractError::ErrAlreadyExists))
+    );
+    ctx.client.create_dispute(&params);
+
+    ctx.client.remove_token(&other);
+    let res = ctx.client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrTokenNotAllowed)));
+    assert_eq!(
+        ctx.client.try_remove_token(&other),
+        Err(Ok(ContractError::ErrNotFound))
+    );
+}
+
+#[test]
+fn test_18_decimal_stakes_settle() {
+    let ctx = setup();
+    let wei = add_usdc(&ctx);
+    let unit = 10i128.pow(18);
+    let claimer = usdc_account(&ctx, &wei, 1_000 * unit);
+    let defender = usdc_account(&ctx, &wei, 1_000 * unit);
+    let mut params = ctx.dispute_params(&claimer, &defender, unit, 100 * unit, 5);
+    params.token = wei.address.clone();
+    let dispute_id = ctx.client.create_dispute(&params);
+    ctx.client.pay_dispute(&claimer, &dispute_id, &(50 * unit));
+    ctx.client.pay_dispute(&defender, &dispute_id, &(50 * unit));
+
+    let votes = [1, 1, 1, 2, 2];
+    let mut jurors = std::vec::Vec::new();
+    for (i, vote) in votes.iter().enumerate() {
+        let juror = usdc_account(&ctx, &wei, 100 * unit);
+        ctx.client
+            .register_juror(&juror, &ctx.category, &wei.address, &(100 * unit));
+        ctx.client
+            .assign_dispute(&juror, &ctx.category, &wei.address, &(100 * unit));
+        ctx.commit(&juror, dispute_id, *vote, i as u8);
+        jurors.push(juror);
+    }
+    for (i, vote) in votes.iter().enumerate() {
+        ctx.reveal(&jurors[i], dispute_id, *vote, i as u8);
+    }
+    assert_eq!(ctx.client.execute(&dispute_id), RulingOutcome::Majority(1));
+
+    // The two slashed stakes and the defender's deposit, less the 5% fee on
+    // them, are shared by the three coherent jurors.
+    let pool = 250 * unit;
+    let reward_each = (pool - pool * 5 / 100) / 3;
+    for juror in jurors.iter().take(3) {
+        assert_eq!(
+            ctx.client
+                .get_juror_stake(juror, &ctx.category, &wei.address)
+                .amount,
+            100 * unit + reward_each
+        );
+    }
+}
@@ -7,16 +7,110 @@ pub enum DisputeStatus {
     Commit = 1,
     Reveal = 2,
     Finished = 3,
+    Cancelled = 4,
+    /// A ruling was reached and the losing party can still appeal it.
+    Appealable = 5,
+    /// Both parties paid and can add evidence. Jurors can join but not commit.
+    Evidence = 6,
 }
 
+/// What to do with a dispute whose panel is short at the commit deadline.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum UnderstaffedPolicy {
+    /// Refund and cancel, or keep the appealed ruling in an appeal round.
+    Refund = 0,
+    /// Keep the current panel and extend the commit window.
+    ExtendCommit = 1,
+    /// Release jurors that have not committed and extend the commit window.
+    Reopen = 2,
+}
+
+/// Penalty for jurors that never committed or revealed.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum NonRevealPenalty {
+    /// Slash the whole stake.
+    FullSlash,
+    /// Slash this many basis points of the stake and return the rest.
+    PartialSlash(u32),
+    /// Return the stake but ban the juror from new disputes for this many seconds.
+    Cooldown(u64),
+}
+
+/// How each juror's stake was settled when the dispute was executed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum JurorOutcome {
+    Pending = 0,
+    Coherent = 1,
+    Incoherent = 2,
+    NotRevealed = 3,
+    NotCommitted = 4,
+}
+
+/// What jurors vote on.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum RulingMode {
+    /// Jurors pick one of the dispute's choices, see `CLAIMER_CHOICE`.
+    Choice = 0,
+    /// Jurors vote the claimer's share of the deposits, in basis points.
+    Split = 1,
+}
+
+/// How a dispute was decided.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub enum RulingOutcome {
+    /// Not executed yet.
+    Pending,
+    /// This choice got more revealed votes than any other.
+    Majority(u32),
+    /// Tie or no reveals, settled by the category's default ruling.
+    DefaultRuling(u32),
+    /// Tie or no reveals, both parties got their deposits back.
+    Refunded,
+    /// Tie, sent to a new round with a larger panel.
+    Escalated,
+    /// Median claimer share of a `RulingMode::Split` dispute, in basis points.
+    Split(u32),
+}
+
+/// What to do when revealed votes are tied or nobody revealed.
+#[contracttype]
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum TiePolicy {
+    /// Refund both parties.
+    Refund = 0,
+    /// Apply the category's default ruling, refunding if it has none.
+    DefaultRuling = 1,
+    /// Start a new round with a larger panel, refunding past `MAX_JURORS`.
+    Appeal = 2,
+}
+
+pub const MAX_BPS: u32 = 10_000;
+/// Bytes in front of the public inputs in a `proof_blob`.
+pub const PROOF_HEADER_BYTES: u32 = 4;
+pub const MAX_JURORS: u32 = 101;
+
+// Rulings. Choice 0 is always available and means the jurors refuse to
+// arbitrate. Choices 1 and 2 award the dispute to the claimer and the
+// defender. Higher choices only mean something to the arbitrable contract,
+// so like a refusal they leave both deposits with the parties.
+pub const CLAIMER_CHOICE: u32 = 1;
+pub const DEFENDER_CHOICE: u32 = 2;
+pub const MAX_CHOICES: u32 = 32;
+/// How many queue entries `assign_dispute` looks at.
+pub const QUEUE_SCAN_LIMIT: u64 = 10;
+/// Picks a draw makes per seat before settling for a short panel.
+pub const DRAW_ATTEMPTS_PER_SEAT: u32 = 4;
+/// Entries a dispute's evidence log can hold.
+pub const MAX_EVIDENCE: u32 = 32;
+
 // Storage keys
 pub const CATEGORIES_KEY: &Symbol = &symbol_short!("CATS");
 pub const CONFIG_KEY: &Symbol = &symbol_short!("CONF");
-pub const DISPUTE_COUNTER_KEY: &Symbol = &symbol_short!("CNTR");
-
-// UltraHonk verifier contract address
-pub const ULTRAHONK_CONTRACT_ADDRESS: &str =
-    "CAXMCB6EYJ6Z6PHHC3MZ54IKHAZV5WSM2OAK4DSGM2E2M6DJG4FX5CPB";
 
 #[contracttype]
 #[derive(Clone)]
@@ -32,12 +126,54 @@ pub struct Dispute {
     pub max_amount: i128,
 
     pub category: Symbol,
+    /// SEP-41 token the deposits, stakes, fees and payouts are in.
+    pub token: Address,
     pub allowed_jurors: Option<Vec<Address>>,
-    pub jurors_required: u32,
+    pub ruling_mode: RulingMode,
+    /// Jurors vote in `0..=num_choices`. Unused by `RulingMode::Split` disputes.
+    pub num_choices: u32,
+    /// Award the only party that paid instead of refunding it.
+    pub default_to_payer: bool,
 
     pub deadline_pay_seconds: u64,
+    /// End of the evidence phase of round 0, zero if there is none.
+    pub deadline_evidence_seconds: u64,
+    /// Commit and reveal windows, counted from the start of each round.
+    pub commit_seconds: u64,
+    pub reveal_seconds: u64,
+
+    /// Index of the current round, see `Round`.
+    pub round: u32,
+    pub deadline_appeal_seconds: u64,
+
+    pub status: DisputeStatus,
+
+    pub claimer_paid: bool,
+    pub defender_paid: bool,
+    pub claimer_amount: i128,
+    pub defender_amount: i128,
+    /// Per-party fee fixed at creation, paid to the final round's coherent jurors.
+    pub arbitration_fee: i128,
+    /// Tokens currently held by the contract on behalf of this dispute.
+    pub escrowed: i128,
+
+    pub winner: Option<Address>,
+    pub outcome: RulingOutcome,
+}
+
+/// One jury round of a dispute, stored under `DataKey::Round`.
+#[contracttype]
+#[derive(Clone)]
+pub struct Round {
+    pub jurors_required: u32,
+
+    /// Jurors drawn when the round opened, seated only if they accept in time.
+    pub drawn_jurors: Vec<Address>,
+    pub deadline_accept_seconds: u64,
+
     pub deadline_commit_seconds: u64,
     pub deadline_reveal_seconds: u64,
+    pub commit_extensions: u32,
 
     pub assigned_jurors: Vec<Address>,
     pub juror_stakes: Vec<i128>,
@@ -45,21 +181,42 @@ pub struct Dispute {
     pub commitments: Vec<Option<BytesN<32>>>,
     pub revealed_votes: Vec<Option<u32>>,
     pub revealed_salts: Vec<Option<BytesN<32>>>,
+    pub juror_outcomes: Vec<JurorOutcome>,
 
-    pub status: DisputeStatus,
+    /// Party that funded this round, `None` for round 0 and escalated ties.
+    pub appellant: Option<Address>,
+    pub appeal_fee: i128,
 
-    pub claimer_paid: bool,
-    pub defender_paid: bool,
-    pub claimer_amount: i128,
-    pub defender_amount: i128,
+    pub outcome: RulingOutcome,
+}
 
-    pub winner: Option<Address>,
+#[contracttype]
+#[derive(Clone)]
+pub struct CreateDisputeParams {
+    pub claimer: Address,
+    pub defender: Address,
+    /// Allowlisted account opening the dispute for the claimer.
+    pub delegate: Option<Address>,
+    pub meta_hash: BytesN<32>,
+    pub min_amount: i128,
+    pub max_amount: i128,
+    pub category: Symbol,
+    /// Must be allowlisted with `add_token`.
+    pub token: Address,
+    pub allowed_jurors: Option<Vec<Address>>,
+    pub jurors_required: u32,
+    pub ruling_mode: RulingMode,
+    pub num_choices: u32,
+    pub limits: TimeLimits,
+    pub default_to_payer: bool,
 }
 
 #[contracttype]
 #[derive(Clone)]
 pub struct TimeLimits {
     pub pay_seconds: u64,
+    /// Zero skips the evidence phase.
+    pub evidence_seconds: u64,
     pub commit_seconds: u64,
     pub reveal_seconds: u64,
 }
@@ -78,9 +235,94 @@ pub struct Config {
     pub min_pay_seconds: u64,
     pub max_pay_seconds: u64,
 
+    /// Both zero until `set_evidence_limits` is called.
+    pub min_evidence_seconds: u64,
+    pub max_evidence_seconds: u64,
+
     pub min_commit_seconds: u64,
     pub max_commit_seconds: u64,
 
     pub min_reveal_seconds: u64,
     pub max_reveal_seconds: u64,
+
+    pub understaffed_policy: UnderstaffedPolicy,
+    pub max_commit_extensions: u32,
+    pub commit_extension_seconds: u64,
+
+    pub non_reveal_penalty: NonRevealPenalty,
+    pub tie_policy: TiePolicy,
+    pub appeal_window_seconds: u64,
+    /// How long drawn jurors have to accept their seat. Zero disables the draw.
+    pub accept_window_seconds: u64,
+    pub unstake_cooldown_seconds: u64,
+    /// UltraHonk verifier used for categories with `verify_proofs` set.
+    pub verifier: Option<Address>,
+    /// How far from the median, in basis points, a coherent split vote can be.
+    pub split_tolerance_bps: u32,
+    /// Share of each slashed pool taken as a protocol fee, in basis points.
+    pub fee_bps: u32,
+    /// Receives protocol fees and anything no juror is left to claim.
+    pub treasury: Address,
+}
+
+/// One entry of a dispute's evidence log.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct Evidence {
+    pub submitter: Address,
+    pub evidence_hash: BytesN<32>,
+    pub submitted_at: u64,
+}
+
+/// A juror's registered stake in one category.
+#[contracttype]
+#[derive(Clone, Debug, PartialEq)]
+pub struct JurorStake {
+    /// Stake held by the registry, including `unstaking`.
+    pub amount: i128,
+    /// Stake currently locked in dispute escrows.
+    pub locked: i128,
+    /// Stake waiting for `unstake_at` before it can be withdrawn.
+    pub unstaking: i128,
+    pub unstake_at: u64,
+}
+
+/// Per-category overrides managed by the admin.
+#[contracttype]
+#[derive(Clone, Default)]
+pub struct CategorySettings {
+    pub default_ruling: Option<u32>,
+    /// Requires every reveal to carry a proof accepted by the verifier.
+    pub verify_proofs: bool,
+    /// Overrides `Config::fee_bps` for disputes in this category.
+    pub fee_bps: Option<u32>,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    DisputeCounter,
+    Dispute(u64),
+    JurorBannedUntil(Address),
+    CategorySettings(Symbol),
+    Round(u64, u32),
+    /// Juror pool per category and token, one entry per juror plus a reverse lookup.
+    PoolSize(Symbol, Address),
+    PoolMember(Symbol, Address, u32),
+    PoolIndex(Symbol, Address, Address),
+    /// Fenwick tree node over the available stake of each pool slot, from one.
+    PoolStake(Symbol, Address, u32),
+    /// Queue of rounds still needing jurors, per category and token.
+    QueueHead(Symbol, Address),
+    QueueTail(Symbol, Address),
+    QueueEntry(Symbol, Address, u64),
+    JurorStake(Symbol, Address, Address),
+    /// Payouts credited to an account in a token and not withdrawn yet.
+    Claimable(Address, Address),
+    /// Allowlisted tokens, mapped to the arbitration fee in that token.
+    AllowedToken(Address),
+    /// Accounts allowed to create disputes on behalf of a claimer.
+    Delegate(Address),
+    /// Evidence log of a dispute, in submission order.
+    Evidence(u64),
 }
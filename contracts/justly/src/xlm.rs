@@ -26,9 +26,6 @@ mod xlm {
     ) -> soroban_sdk::token::StellarAssetClient<'a> {
         soroban_sdk::token::StellarAssetClient::new(&env, &contract_id(env))
     }
-    pub fn token_client<'a>(env: &soroban_sdk::Env) -> soroban_sdk::token::TokenClient<'a> {
-        soroban_sdk::token::TokenClient::new(&env, &contract_id(env))
-    }
 }
 const ONE_XLM: i128 = 1_000_000_0;
 
@@ -14,4 +14,8 @@ pub enum ContractError {
     ErrAlreadyExecuted = 10,
     ErrConfigMissing = 11,
     ErrRemoteMissing = 12,
+    ErrPaymentClosed = 13,
+    ErrEvidenceClosed = 14,
+    ErrTooEarly = 15,
+    ErrCancelled = 16,
 }
@@ -0,0 +1,71 @@
+use soroban_sdk::{contractevent, Address, BytesN, Symbol};
+
+#[contractevent(topics = ["relayer"], data_format = "single-value")]
+pub struct RelayerSet {
+    pub relayer: Address,
+}
+
+#[contractevent(topics = ["CREATED"], data_format = "vec")]
+pub struct DisputeCreated {
+    #[topic]
+    pub dispute_id: u64,
+    pub arbitrable: Address,
+    pub claimer: Address,
+    pub defender: Address,
+    pub category: Symbol,
+    pub jurors_required: u32,
+    pub num_choices: u32,
+    pub required_amount: i128,
+}
+
+#[contractevent(topics = ["PAID"], data_format = "vec")]
+pub struct DisputePaid {
+    #[topic]
+    pub dispute_id: u64,
+    pub payer: Address,
+    pub amount: i128,
+}
+
+#[contractevent(topics = ["EVIDENCE"], data_format = "vec")]
+pub struct EvidenceSubmitted {
+    #[topic]
+    pub dispute_id: u64,
+    pub submitter: Address,
+    pub evidence_hash: BytesN<32>,
+}
+
+/// The local dispute was tied to `remote_dispute_id` in the court.
+#[contractevent(topics = ["BOUND"], data_format = "single-value")]
+pub struct DisputeBound {
+    #[topic]
+    pub dispute_id: u64,
+    pub remote_dispute_id: u64,
+}
+
+#[contractevent(topics = ["RULING"], data_format = "single-value")]
+pub struct RulingReceived {
+    #[topic]
+    pub dispute_id: u64,
+    pub ruling: u32,
+}
+
+#[contractevent(topics = ["EXECUTE"], data_format = "single-value")]
+pub struct RulingExecuted {
+    #[topic]
+    pub dispute_id: u64,
+    pub ruling: u32,
+}
+
+#[contractevent(topics = ["CANCEL"], data_format = "single-value")]
+pub struct DisputeCancelled {
+    #[topic]
+    pub dispute_id: u64,
+}
+
+#[contractevent(topics = ["PAYOUT"], data_format = "vec")]
+pub struct Payout {
+    #[topic]
+    pub dispute_id: u64,
+    pub to: Address,
+    pub amount: i128,
+}
@@ -1,13 +1,16 @@
 #![no_std]
 
 use error::ContractError;
-use soroban_sdk::{contract, contractimpl, Address, BytesN, Env, IntoVal, Symbol};
+use soroban_sdk::{
+    contract, contractimpl, token::TokenClient, Address, BytesN, Env, IntoVal, Symbol,
+};
 use types::{
-    Config, CreateDisputeParams, DisputeStatus, ProxyDispute, BOUND_TOPIC, CREATED_TOPIC,
-    EVIDENCE_TOPIC, EXECUTED_TOPIC, PAID_TOPIC, RULING_TOPIC,
+    Config, CreateDisputeParams, DisputeStatus, ProxyDispute, CLAIMER_CHOICE, DEFENDER_CHOICE,
+    MAX_CHOICES,
 };
 
 mod error;
+mod events;
 mod storage;
 mod types;
 
@@ -19,9 +22,16 @@ pub struct JustlyProxy;
 
 #[contractimpl]
 impl JustlyProxy {
-    pub fn __constructor(env: Env, admin: Address, relayer: Address) {
+    pub fn __constructor(env: Env, admin: Address, relayer: Address, token: Address) {
         admin.require_auth();
-        storage::set_config(&env, &Config { admin, relayer });
+        storage::set_config(
+            &env,
+            &Config {
+                admin,
+                relayer,
+                token,
+            },
+        );
         storage::set_counter(&env, 0);
     }
 
@@ -30,8 +40,7 @@ impl JustlyProxy {
         let mut cfg = storage::get_config(&env)?;
         cfg.relayer = relayer.clone();
         storage::set_config(&env, &cfg);
-        env.events()
-            .publish((Symbol::new(&env, "relayer"),), relayer);
+        events::RelayerSet { relayer }.publish(&env);
         Ok(())
     }
 
@@ -40,11 +49,20 @@ impl JustlyProxy {
 
         if params.claimer == params.defender
             || params.jurors_required == 0
+            || !(2..=MAX_CHOICES).contains(&params.num_choices)
             || params.required_amount <= 0
         {
             return Err(ContractError::ErrInvalidInput);
         }
 
+        let created_at = env.ledger().timestamp();
+        let deadline_pay_seconds = created_at
+            .checked_add(params.pay_seconds)
+            .ok_or(ContractError::ErrInvalidInput)?;
+        let deadline_evidence_seconds = deadline_pay_seconds
+            .checked_add(params.evidence_seconds)
+            .ok_or(ContractError::ErrInvalidInput)?;
+
         let id = storage::next_dispute_id(&env);
         let dispute = ProxyDispute {
             id,
@@ -54,34 +72,38 @@ impl JustlyProxy {
             category: params.category.clone(),
             root_evidence_hash: params.root_evidence_hash,
             jurors_required: params.jurors_required,
+            num_choices: params.num_choices,
             pay_seconds: params.pay_seconds,
             evidence_seconds: params.evidence_seconds,
             commit_seconds: params.commit_seconds,
             reveal_seconds: params.reveal_seconds,
+            deadline_pay_seconds,
+            deadline_evidence_seconds,
             required_amount: params.required_amount,
             claimer_paid: false,
             defender_paid: false,
             claimer_amount: 0,
             defender_amount: 0,
+            escrowed: 0,
             remote_dispute_id: None,
             ruling: None,
             rule_executed: false,
             status: DisputeStatus::Created,
-            created_at: env.ledger().timestamp(),
+            created_at,
         };
 
         storage::set_dispute(&env, &dispute);
-        env.events().publish(
-            (CREATED_TOPIC, id),
-            (
-                params.arbitrable,
-                params.claimer,
-                params.defender,
-                params.category,
-                params.jurors_required,
-                params.required_amount,
-            ),
-        );
+        events::DisputeCreated {
+            dispute_id: id,
+            arbitrable: params.arbitrable,
+            claimer: params.claimer,
+            defender: params.defender,
+            category: params.category,
+            jurors_required: params.jurors_required,
+            num_choices: params.num_choices,
+            required_amount: params.required_amount,
+        }
+        .publish(&env);
         Ok(id)
     }
 
@@ -94,6 +116,10 @@ impl JustlyProxy {
         payer.require_auth();
         let mut dispute = storage::get_dispute(&env, dispute_id)?;
 
+        if env.ledger().timestamp() > dispute.deadline_pay_seconds {
+            return Err(ContractError::ErrPaymentClosed);
+        }
+
         if amount != dispute.required_amount {
             return Err(ContractError::ErrInvalidAmount);
         }
@@ -114,13 +140,25 @@ impl JustlyProxy {
             return Err(ContractError::ErrUnauthorized);
         }
 
+        let cfg = storage::get_config(&env)?;
+        TokenClient::new(&env, &cfg.token).transfer(
+            &payer,
+            env.current_contract_address(),
+            &amount,
+        );
+        dispute.escrowed += amount;
+
         if dispute.claimer_paid && dispute.defender_paid {
             dispute.status = DisputeStatus::Funded;
         }
 
         storage::set_dispute(&env, &dispute);
-        env.events()
-            .publish((PAID_TOPIC, dispute_id), (payer, amount));
+        events::DisputePaid {
+            dispute_id,
+            payer,
+            amount,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -140,8 +178,20 @@ impl JustlyProxy {
             return Err(ContractError::ErrUnauthorized);
         }
 
-        env.events()
-            .publish((EVIDENCE_TOPIC, dispute_id), (submitter, evidence_hash));
+        if matches!(
+            dispute.status,
+            DisputeStatus::Ruled | DisputeStatus::Executed | DisputeStatus::Cancelled
+        ) || env.ledger().timestamp() > dispute.deadline_evidence_seconds
+        {
+            return Err(ContractError::ErrEvidenceClosed);
+        }
+
+        events::EvidenceSubmitted {
+            dispute_id,
+            submitter,
+            evidence_hash,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -153,6 +203,10 @@ impl JustlyProxy {
         require_relayer(&env)?;
         let mut dispute = storage::get_dispute(&env, local_dispute_id)?;
 
+        if dispute.status == DisputeStatus::Cancelled {
+            return Err(ContractError::ErrCancelled);
+        }
+
         if dispute.remote_dispute_id.is_some() {
             return Err(ContractError::ErrAlreadyBound);
         }
@@ -165,8 +219,11 @@ impl JustlyProxy {
         storage::set_dispute(&env, &dispute);
         storage::set_remote_binding(&env, remote_dispute_id, local_dispute_id);
 
-        env.events()
-            .publish((BOUND_TOPIC, local_dispute_id), remote_dispute_id);
+        events::DisputeBound {
+            dispute_id: local_dispute_id,
+            remote_dispute_id,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -174,7 +231,11 @@ impl JustlyProxy {
         require_relayer(&env)?;
         let mut dispute = storage::get_dispute(&env, local_dispute_id)?;
 
-        if ruling > 1 {
+        if dispute.status == DisputeStatus::Cancelled {
+            return Err(ContractError::ErrCancelled);
+        }
+
+        if ruling > dispute.num_choices {
             return Err(ContractError::ErrInvalidInput);
         }
 
@@ -189,11 +250,18 @@ impl JustlyProxy {
         dispute.ruling = Some(ruling);
         dispute.status = DisputeStatus::Ruled;
         storage::set_dispute(&env, &dispute);
-        env.events()
-            .publish((RULING_TOPIC, local_dispute_id), ruling);
+        events::RulingReceived {
+            dispute_id: local_dispute_id,
+            ruling,
+        }
+        .publish(&env);
         Ok(())
     }
 
+    /// Passes the ruling on to the arbitrable contract and releases the
+    /// deposits. On a funded dispute a ruling for the claimer or the defender
+    /// sends both deposits to that party. Any other ruling, or a dispute that
+    /// was never fully funded, refunds each party its own deposit.
     pub fn execute_rule(env: Env, local_dispute_id: u64) -> Result<(), ContractError> {
         let mut dispute = storage::get_dispute(&env, local_dispute_id)?;
 
@@ -207,11 +275,62 @@ impl JustlyProxy {
 
         env.invoke_contract::<()>(&dispute.arbitrable, &fn_name, args);
 
+        let funded = dispute.claimer_paid && dispute.defender_paid;
+        let winner = match ruling {
+            CLAIMER_CHOICE if funded => Some(dispute.claimer.clone()),
+            DEFENDER_CHOICE if funded => Some(dispute.defender.clone()),
+            _ => None,
+        };
+        match winner {
+            Some(winner) => {
+                let total = dispute.escrowed;
+                release(&env, &mut dispute, &winner, total)?;
+            }
+            None => refund_deposits(&env, &mut dispute)?,
+        }
+
         dispute.rule_executed = true;
         dispute.status = DisputeStatus::Executed;
         storage::set_dispute(&env, &dispute);
-        env.events()
-            .publish((EXECUTED_TOPIC, local_dispute_id), ruling);
+        events::RulingExecuted {
+            dispute_id: local_dispute_id,
+            ruling,
+        }
+        .publish(&env);
+        Ok(())
+    }
+
+    /// Cancels a dispute the court never took up and refunds the deposits.
+    /// Anyone can call it once the payment deadline has passed without both
+    /// parties paying, or once the evidence deadline has passed without the
+    /// relayer binding the dispute to a court dispute.
+    pub fn refund(env: Env, local_dispute_id: u64) -> Result<(), ContractError> {
+        let mut dispute = storage::get_dispute(&env, local_dispute_id)?;
+
+        if dispute.status == DisputeStatus::Cancelled {
+            return Err(ContractError::ErrCancelled);
+        }
+
+        if dispute.ruling.is_some() {
+            return Err(ContractError::ErrRulingAlreadySet);
+        }
+
+        let now = env.ledger().timestamp();
+        let unfunded =
+            !(dispute.claimer_paid && dispute.defender_paid) && now > dispute.deadline_pay_seconds;
+        let unbound =
+            dispute.remote_dispute_id.is_none() && now > dispute.deadline_evidence_seconds;
+        if !unfunded && !unbound {
+            return Err(ContractError::ErrTooEarly);
+        }
+
+        refund_deposits(&env, &mut dispute)?;
+        dispute.status = DisputeStatus::Cancelled;
+        storage::set_dispute(&env, &dispute);
+        events::DisputeCancelled {
+            dispute_id: local_dispute_id,
+        }
+        .publish(&env);
         Ok(())
     }
 
@@ -226,6 +345,18 @@ impl JustlyProxy {
     pub fn get_relayer(env: Env) -> Result<Address, ContractError> {
         Ok(storage::get_config(&env)?.relayer)
     }
+
+    pub fn get_token(env: Env) -> Result<Address, ContractError> {
+        Ok(storage::get_config(&env)?.token)
+    }
+
+    /// Extends the TTL of a dispute and its remote binding. Anyone can call it
+    /// to keep a dispute alive until it is ruled and executed.
+    pub fn bump_dispute_ttl(env: Env, local_dispute_id: u64) -> Result<(), ContractError> {
+        let dispute = storage::get_dispute(&env, local_dispute_id)?;
+        storage::extend_dispute_ttl(&env, &dispute);
+        Ok(())
+    }
 }
 
 fn require_admin(env: &Env) -> Result<(), ContractError> {
@@ -234,6 +365,42 @@ fn require_admin(env: &Env) -> Result<(), ContractError> {
     Ok(())
 }
 
+/// Sends `amount` of the dispute's deposits to `to` and announces it with a
+/// `PAYOUT` event.
+fn release(
+    env: &Env,
+    dispute: &mut ProxyDispute,
+    to: &Address,
+    amount: i128,
+) -> Result<(), ContractError> {
+    if amount == 0 {
+        return Ok(());
+    }
+    let cfg = storage::get_config(env)?;
+    TokenClient::new(env, &cfg.token).transfer(&env.current_contract_address(), to, &amount);
+    dispute.escrowed -= amount;
+    events::Payout {
+        dispute_id: dispute.id,
+        to: to.clone(),
+        amount,
+    }
+    .publish(env);
+    Ok(())
+}
+
+/// Returns each party the deposit it paid.
+fn refund_deposits(env: &Env, dispute: &mut ProxyDispute) -> Result<(), ContractError> {
+    if dispute.claimer_paid {
+        let claimer = dispute.claimer.clone();
+        release(env, dispute, &claimer, dispute.claimer_amount)?;
+    }
+    if dispute.defender_paid {
+        let defender = dispute.defender.clone();
+        release(env, dispute, &defender, dispute.defender_amount)?;
+    }
+    Ok(())
+}
+
 fn require_relayer(env: &Env) -> Result<(), ContractError> {
     let cfg = storage::get_config(env)?;
     cfg.relayer.require_auth();
@@ -1,9 +1,11 @@
 use crate::error::ContractError;
 use crate::types::{Config, DataKey, ProxyDispute};
+use justly_storage::{extend_instance, extend_persistent, get_persistent, set_persistent};
 use soroban_sdk::Env;
 
 pub fn set_config(env: &Env, cfg: &Config) {
     env.storage().instance().set(&DataKey::Config, cfg);
+    extend_instance(env);
 }
 
 pub fn get_config(env: &Env) -> Result<Config, ContractError> {
@@ -14,14 +16,11 @@ pub fn get_config(env: &Env) -> Result<Config, ContractError> {
 }
 
 pub fn set_counter(env: &Env, count: u64) {
-    env.storage().instance().set(&DataKey::Counter, &count);
+    set_persistent(env, &DataKey::Counter, &count);
 }
 
 pub fn get_counter(env: &Env) -> u64 {
-    env.storage()
-        .instance()
-        .get(&DataKey::Counter)
-        .unwrap_or(0u64)
+    get_persistent(env, &DataKey::Counter).unwrap_or(0u64)
 }
 
 pub fn next_dispute_id(env: &Env) -> u64 {
@@ -31,26 +30,30 @@ pub fn next_dispute_id(env: &Env) -> u64 {
 }
 
 pub fn set_dispute(env: &Env, dispute: &ProxyDispute) {
-    env.storage()
-        .instance()
-        .set(&DataKey::Dispute(dispute.id), dispute);
+    set_persistent(env, &DataKey::Dispute(dispute.id), dispute);
 }
 
 pub fn get_dispute(env: &Env, dispute_id: u64) -> Result<ProxyDispute, ContractError> {
-    env.storage()
-        .instance()
-        .get(&DataKey::Dispute(dispute_id))
-        .ok_or(ContractError::ErrNotFound)
+    get_persistent(env, &DataKey::Dispute(dispute_id)).ok_or(ContractError::ErrNotFound)
+}
+
+/// Extends the TTL of a dispute, of its remote binding, if any, and of the
+/// instance.
+pub fn extend_dispute_ttl(env: &Env, dispute: &ProxyDispute) {
+    extend_instance(env);
+    extend_persistent(env, &DataKey::Dispute(dispute.id));
+    if let Some(remote_dispute_id) = dispute.remote_dispute_id {
+        extend_persistent(env, &DataKey::RemoteToLocal(remote_dispute_id));
+    }
 }
 
 pub fn get_local_id_by_remote(env: &Env, remote_dispute_id: u64) -> Option<u64> {
-    env.storage()
-        .instance()
-        .get(&DataKey::RemoteToLocal(remote_dispute_id))
+    get_persistent(env, &DataKey::RemoteToLocal(remote_dispute_id))
 }
 
 pub fn set_remote_binding(env: &Env, remote_dispute_id: u64, local_dispute_id: u64) {
-    env.storage().instance().set(
+    set_persistent(
+        env,
         &DataKey::RemoteToLocal(remote_dispute_id),
         &local_dispute_id,
     );
@@ -2,10 +2,19 @@
 
 extern crate std;
 
+use crate::error::ContractError;
 use crate::tests::mock_arbitrable::{MockArbitrable, MockArbitrableClient};
-use crate::types::{CreateDisputeParams, DisputeStatus};
+use crate::types::{CreateDisputeParams, DataKey, DisputeStatus, MAX_CHOICES};
 use crate::{JustlyProxy, JustlyProxyClient};
-use soroban_sdk::{testutils::Address as _, Address, BytesN, Env, Symbol};
+use justly_storage::{DAY_IN_LEDGERS, TTL_EXTEND_TO};
+use soroban_sdk::{
+    testutils::{
+        storage::{Instance, Persistent},
+        Address as _, Ledger,
+    },
+    token::{StellarAssetClient, TokenClient},
+    Address, BytesN, Env, Symbol,
+};
 
 fn setup() -> Env {
     let env = Env::default();
@@ -14,10 +23,41 @@ fn setup() -> Env {
 }
 
 fn deploy_proxy<'a>(env: &Env, admin: &Address, relayer: &Address) -> JustlyProxyClient<'a> {
-    let id = env.register(JustlyProxy, (admin.clone(), relayer.clone()));
+    let token = env.register_stellar_asset_contract_v2(admin.clone());
+    let id = env.register(
+        JustlyProxy,
+        (admin.clone(), relayer.clone(), token.address()),
+    );
     JustlyProxyClient::new(env, &id)
 }
 
+/// Mints `amount` of the proxy's deposit token to `account`.
+fn fund(env: &Env, client: &JustlyProxyClient, account: &Address, amount: i128) {
+    StellarAssetClient::new(env, &client.get_token()).mint(account, &amount);
+}
+
+fn balance(env: &Env, client: &JustlyProxyClient, account: &Address) -> i128 {
+    TokenClient::new(env, &client.get_token()).balance(account)
+}
+
+/// Creates a dispute over `amount` and has both parties pay it.
+fn funded_dispute(
+    env: &Env,
+    client: &JustlyProxyClient,
+    arbitrable: &Address,
+    claimer: &Address,
+    defender: &Address,
+    amount: i128,
+) -> u64 {
+    fund(env, client, claimer, amount);
+    fund(env, client, defender, amount);
+    let params = create_params(env, arbitrable, claimer, defender, amount);
+    let dispute_id = client.create_dispute(&params);
+    client.pay_dispute(claimer, &dispute_id, &amount);
+    client.pay_dispute(defender, &dispute_id, &amount);
+    dispute_id
+}
+
 fn deploy_mock_arbitrable<'a>(env: &Env) -> MockArbitrableClient<'a> {
     let id = env.register(MockArbitrable, ());
     MockArbitrableClient::new(env, &id)
@@ -37,6 +77,7 @@ fn create_params(
         category: Symbol::new(env, "General"),
         root_evidence_hash: BytesN::from_array(env, &[1u8; 32]),
         jurors_required: 5,
+        num_choices: 2,
         pay_seconds: 3600,
         evidence_seconds: 3600,
         commit_seconds: 3600,
@@ -117,6 +158,9 @@ fn test_pay_dispute_requires_exact_amount_and_full_funding() {
     let client = deploy_proxy(&env, &admin, &relayer);
 
     let amount = 5_000_000;
+    fund(&env, &client, &claimer, amount);
+    fund(&env, &client, &defender, amount);
+    fund(&env, &client, &third_party, amount);
     let params = create_params(&env, &arbitrable.address, &claimer, &defender, amount);
     let dispute_id = client.create_dispute(&params);
 
@@ -141,6 +185,12 @@ fn test_pay_dispute_requires_exact_amount_and_full_funding() {
     assert!(dispute.claimer_paid);
     assert!(dispute.defender_paid);
     assert!(dispute.status == DisputeStatus::Funded);
+
+    // Both deposits are held by the proxy.
+    assert_eq!(dispute.escrowed, 2 * amount);
+    assert_eq!(balance(&env, &client, &client.address), 2 * amount);
+    assert_eq!(balance(&env, &client, &claimer), 0);
+    assert_eq!(balance(&env, &client, &third_party), amount);
 }
 
 #[test]
@@ -166,6 +216,206 @@ fn test_submit_evidence_permissions() {
     assert!(res.is_err());
 }
 
+#[test]
+fn test_deadlines_run_back_to_back_from_creation() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let defender = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+
+    env.ledger().with_mut(|li| li.timestamp = 1_000);
+    let mut params = create_params(&env, &arbitrable.address, &claimer, &defender, 1_000_000);
+    params.evidence_seconds = 7200;
+    let dispute_id = client.create_dispute(&params);
+
+    let dispute = client.get_dispute(&dispute_id);
+    assert_eq!(dispute.created_at, 1_000);
+    assert_eq!(dispute.deadline_pay_seconds, 4_600);
+    assert_eq!(dispute.deadline_evidence_seconds, 11_800);
+
+    params.pay_seconds = u64::MAX;
+    let res = client.try_create_dispute(&params);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidInput)));
+}
+
+#[test]
+fn test_late_payment_is_rejected() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let defender = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+
+    fund(&env, &client, &claimer, 1_000_000);
+    fund(&env, &client, &defender, 1_000_000);
+    let params = create_params(&env, &arbitrable.address, &claimer, &defender, 1_000_000);
+    let dispute_id = client.create_dispute(&params);
+
+    env.ledger().with_mut(|li| li.timestamp += 3600);
+    client.pay_dispute(&claimer, &dispute_id, &1_000_000);
+
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    let res = client.try_pay_dispute(&defender, &dispute_id, &1_000_000);
+    assert_eq!(res, Err(Ok(ContractError::ErrPaymentClosed)));
+    assert!(!client.get_dispute(&dispute_id).defender_paid);
+}
+
+#[test]
+fn test_evidence_closes_at_deadline_or_ruling() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let defender = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+    let evidence = BytesN::from_array(&env, &[2u8; 32]);
+
+    let params = create_params(&env, &arbitrable.address, &claimer, &defender, 1_000_000);
+    let late = client.create_dispute(&params);
+    let ruled = client.create_dispute(&params);
+
+    // A ruling closes evidence early.
+    client.bind_remote_dispute(&ruled, &7);
+    client.rule(&ruled, &1);
+    let res = client.try_submit_evidence(&defender, &ruled, &evidence);
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceClosed)));
+
+    client.execute_rule(&ruled);
+    let res = client.try_submit_evidence(&arbitrable.address, &ruled, &evidence);
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceClosed)));
+
+    // Otherwise it stays open through the evidence phase that follows
+    // payment.
+    env.ledger().with_mut(|li| li.timestamp += 7200);
+    client.submit_evidence(&claimer, &late, &evidence);
+
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    let res = client.try_submit_evidence(&defender, &late, &evidence);
+    assert_eq!(res, Err(Ok(ContractError::ErrEvidenceClosed)));
+}
+
+#[test]
+fn test_execute_rule_releases_deposits_by_ruling() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+    let amount = 1_000_000;
+
+    // Rulings 1 and 2 award both deposits, anything else refunds them.
+    for (ruling, claimer_gets, defender_gets) in
+        [(1, 2 * amount, 0), (2, 0, 2 * amount), (0, amount, amount)]
+    {
+        let claimer = Address::generate(&env);
+        let defender = Address::generate(&env);
+        let dispute_id = funded_dispute(
+            &env,
+            &client,
+            &arbitrable.address,
+            &claimer,
+            &defender,
+            amount,
+        );
+        client.bind_remote_dispute(&dispute_id, &(100 + ruling as u64));
+        client.rule(&dispute_id, &ruling);
+        assert_eq!(balance(&env, &client, &client.address), 2 * amount);
+
+        client.execute_rule(&dispute_id);
+        assert_eq!(balance(&env, &client, &claimer), claimer_gets);
+        assert_eq!(balance(&env, &client, &defender), defender_gets);
+        assert_eq!(balance(&env, &client, &client.address), 0);
+        assert_eq!(client.get_dispute(&dispute_id).escrowed, 0);
+    }
+}
+
+#[test]
+fn test_refund_unfunded_dispute() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let defender = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+
+    fund(&env, &client, &claimer, 1_000_000);
+    let params = create_params(&env, &arbitrable.address, &claimer, &defender, 1_000_000);
+    let dispute_id = client.create_dispute(&params);
+    client.pay_dispute(&claimer, &dispute_id, &1_000_000);
+
+    let res = client.try_refund(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    env.ledger().with_mut(|li| li.timestamp += 3601);
+    client.refund(&dispute_id);
+    assert_eq!(balance(&env, &client, &claimer), 1_000_000);
+    assert_eq!(balance(&env, &client, &client.address), 0);
+
+    let dispute = client.get_dispute(&dispute_id);
+    assert!(dispute.status == DisputeStatus::Cancelled);
+    assert_eq!(dispute.escrowed, 0);
+
+    // A cancelled dispute can't be refunded again or taken to court.
+    let res = client.try_refund(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrCancelled)));
+    let res = client.try_bind_remote_dispute(&dispute_id, &1);
+    assert_eq!(res, Err(Ok(ContractError::ErrCancelled)));
+}
+
+#[test]
+fn test_refund_unbound_dispute() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let claimer = Address::generate(&env);
+    let defender = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+
+    let dispute_id = funded_dispute(
+        &env,
+        &client,
+        &arbitrable.address,
+        &claimer,
+        &defender,
+        1_000_000,
+    );
+    let bound = funded_dispute(
+        &env,
+        &client,
+        &arbitrable.address,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        1_000_000,
+    );
+    client.bind_remote_dispute(&bound, &9);
+
+    // Funded disputes wait for the relayer until the evidence deadline.
+    env.ledger().with_mut(|li| li.timestamp += 7200);
+    let res = client.try_refund(&dispute_id);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    env.ledger().with_mut(|li| li.timestamp += 1);
+    client.refund(&dispute_id);
+    assert_eq!(balance(&env, &client, &claimer), 1_000_000);
+    assert_eq!(balance(&env, &client, &defender), 1_000_000);
+    assert_eq!(balance(&env, &client, &client.address), 2_000_000);
+
+    let res = client.try_refund(&bound);
+    assert_eq!(res, Err(Ok(ContractError::ErrTooEarly)));
+
+    client.rule(&bound, &1);
+    let res = client.try_refund(&bound);
+    assert_eq!(res, Err(Ok(ContractError::ErrRulingAlreadySet)));
+}
+
 #[test]
 fn test_remote_binding_and_lookup() {
     let env = setup();
@@ -224,7 +474,7 @@ fn test_rule_requires_remote_and_allows_single_assignment() {
 
     client.bind_remote_dispute(&dispute_id, &501);
 
-    let res = client.try_rule(&dispute_id, &2);
+    let res = client.try_rule(&dispute_id, &3);
     assert!(res.is_err());
 
     client.rule(&dispute_id, &1);
@@ -266,3 +516,110 @@ fn test_execute_rule_calls_arbitrable_and_is_idempotent() {
     let res = client.try_execute_rule(&dispute_id);
     assert!(res.is_err());
 }
+
+#[test]
+fn test_rule_carries_multi_choice_ruling_to_arbitrable() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+
+    let mut params = create_params(
+        &env,
+        &arbitrable.address,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        1_000_000,
+    );
+    for num_choices in [0, 1, MAX_CHOICES + 1] {
+        params.num_choices = num_choices;
+        let res = client.try_create_dispute(&params);
+        assert_eq!(res, Err(Ok(ContractError::ErrInvalidInput)));
+    }
+
+    params.num_choices = 3;
+    let three_way = client.create_dispute(&params);
+    client.bind_remote_dispute(&three_way, &801);
+    let res = client.try_rule(&three_way, &4);
+    assert_eq!(res, Err(Ok(ContractError::ErrInvalidInput)));
+
+    client.rule(&three_way, &3);
+    client.execute_rule(&three_way);
+    assert_eq!(arbitrable.last_rule(), Some((three_way, 3)));
+
+    // Choice 0 is a refusal to arbitrate and is passed on like any ruling.
+    let refused = client.create_dispute(&params);
+    client.bind_remote_dispute(&refused, &802);
+    client.rule(&refused, &0);
+    client.execute_rule(&refused);
+    assert_eq!(arbitrable.last_rule(), Some((refused, 0)));
+}
+
+#[test]
+fn test_thousands_of_disputes_stay_out_of_instance_storage() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+    let params = create_params(
+        &env,
+        &arbitrable.address,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        1_000_000,
+    );
+
+    let instance_entries =
+        || env.as_contract(&client.address, || env.storage().instance().all().len());
+
+    client.create_dispute(&params);
+    let baseline = instance_entries();
+
+    for _ in 0..2_000 {
+        env.cost_estimate().budget().reset_default();
+        client.create_dispute(&params);
+    }
+
+    assert_eq!(instance_entries(), baseline);
+    assert_eq!(client.get_dispute(&2_001).id, 2_001);
+}
+
+#[test]
+fn test_bump_dispute_ttl_extends_dispute_and_binding() {
+    let env = setup();
+    let admin = Address::generate(&env);
+    let relayer = Address::generate(&env);
+    let arbitrable = deploy_mock_arbitrable(&env);
+    let client = deploy_proxy(&env, &admin, &relayer);
+    let params = create_params(
+        &env,
+        &arbitrable.address,
+        &Address::generate(&env),
+        &Address::generate(&env),
+        1_000_000,
+    );
+    let dispute_id = client.create_dispute(&params);
+    client.bind_remote_dispute(&dispute_id, &100);
+
+    let ttl = |key: DataKey| {
+        env.as_contract(&client.address, || env.storage().persistent().get_ttl(&key))
+    };
+
+    env.ledger()
+        .with_mut(|li| li.sequence_number += 2 * DAY_IN_LEDGERS);
+    assert_eq!(
+        ttl(DataKey::Dispute(dispute_id)),
+        TTL_EXTEND_TO - 2 * DAY_IN_LEDGERS
+    );
+
+    client.bump_dispute_ttl(&dispute_id);
+    assert_eq!(ttl(DataKey::Dispute(dispute_id)), TTL_EXTEND_TO);
+    assert_eq!(ttl(DataKey::RemoteToLocal(100)), TTL_EXTEND_TO);
+    let instance_ttl = env.as_contract(&client.address, || env.storage().instance().get_ttl());
+    assert_eq!(instance_ttl, TTL_EXTEND_TO);
+
+    let res = client.try_bump_dispute_ttl(&42);
+    assert!(res.is_err());
+}
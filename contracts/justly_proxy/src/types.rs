@@ -1,4 +1,4 @@
-use soroban_sdk::{contracttype, symbol_short, Address, BytesN, Symbol};
+use soroban_sdk::{contracttype, Address, BytesN, Symbol};
 
 #[contracttype]
 #[derive(Clone, Copy, PartialEq, Eq)]
@@ -7,6 +7,8 @@ pub enum DisputeStatus {
     Funded = 1,
     Ruled = 2,
     Executed = 3,
+    /// Refunded without a ruling, see `JustlyProxy::refund`.
+    Cancelled = 4,
 }
 
 #[contracttype]
@@ -14,6 +16,8 @@ pub enum DisputeStatus {
 pub struct Config {
     pub admin: Address,
     pub relayer: Address,
+    /// SEP-41 token the deposits are paid in.
+    pub token: Address,
 }
 
 #[contracttype]
@@ -26,15 +30,23 @@ pub struct ProxyDispute {
     pub category: Symbol,
     pub root_evidence_hash: BytesN<32>,
     pub jurors_required: u32,
+    /// Rulings range over `0..=num_choices`, 0 being a refusal to arbitrate.
+    pub num_choices: u32,
+    /// Phase lengths, run back to back from `created_at`.
     pub pay_seconds: u64,
     pub evidence_seconds: u64,
     pub commit_seconds: u64,
     pub reveal_seconds: u64,
+    /// Last timestamps at which the parties can pay and submit evidence.
+    pub deadline_pay_seconds: u64,
+    pub deadline_evidence_seconds: u64,
     pub required_amount: i128,
     pub claimer_paid: bool,
     pub defender_paid: bool,
     pub claimer_amount: i128,
     pub defender_amount: i128,
+    /// Deposits currently held by the proxy for this dispute.
+    pub escrowed: i128,
     pub remote_dispute_id: Option<u64>,
     pub ruling: Option<u32>,
     pub rule_executed: bool,
@@ -51,6 +63,7 @@ pub struct CreateDisputeParams {
     pub category: Symbol,
     pub root_evidence_hash: BytesN<32>,
     pub jurors_required: u32,
+    pub num_choices: u32,
     pub pay_seconds: u64,
     pub evidence_seconds: u64,
     pub commit_seconds: u64,
@@ -67,9 +80,9 @@ pub enum DataKey {
     RemoteToLocal(u64),
 }
 
-pub const CREATED_TOPIC: &Symbol = &symbol_short!("CREATED");
-pub const PAID_TOPIC: &Symbol = &symbol_short!("PAID");
-pub const EVIDENCE_TOPIC: &Symbol = &symbol_short!("EVIDENCE");
-pub const BOUND_TOPIC: &Symbol = &symbol_short!("BOUND");
-pub const RULING_TOPIC: &Symbol = &symbol_short!("RULING");
-pub const EXECUTED_TOPIC: &Symbol = &symbol_short!("EXECUTE");
+// Rulings that award the deposits to one party, matching the court contract.
+pub const CLAIMER_CHOICE: u32 = 1;
+pub const DEFENDER_CHOICE: u32 = 2;
+
+/// Upper bound on `num_choices`, matching the court contract.
+pub const MAX_CHOICES: u32 = 32;
@@ -0,0 +1,49 @@
+#![no_std]
+//! Storage TTL handling shared by the Justly contracts.
+//!
+//! Persistent entries and the contract instance are extended to about 30
+//! days whenever they are touched, once they drop below 29 days. Every write
+//! goes through here, so any call that changes state keeps the instance, and
+//! with it the config and the code, alive.
+
+use soroban_sdk::{Env, IntoVal, TryFromVal, Val};
+
+pub const DAY_IN_LEDGERS: u32 = 17_280;
+pub const TTL_THRESHOLD: u32 = 29 * DAY_IN_LEDGERS;
+pub const TTL_EXTEND_TO: u32 = 30 * DAY_IN_LEDGERS;
+
+/// Reads a persistent entry, extending its TTL if it exists.
+pub fn get_persistent<K, V>(env: &Env, key: &K) -> Option<V>
+where
+    K: IntoVal<Env, Val>,
+    V: TryFromVal<Env, Val>,
+{
+    let value = env.storage().persistent().get(key);
+    if value.is_some() {
+        extend_persistent(env, key);
+    }
+    value
+}
+
+/// Writes a persistent entry and extends its TTL and the instance's.
+pub fn set_persistent<K, V>(env: &Env, key: &K, value: &V)
+where
+    K: IntoVal<Env, Val>,
+    V: IntoVal<Env, Val>,
+{
+    env.storage().persistent().set(key, value);
+    extend_persistent(env, key);
+    extend_instance(env);
+}
+
+pub fn extend_persistent<K: IntoVal<Env, Val>>(env: &Env, key: &K) {
+    env.storage()
+        .persistent()
+        .extend_ttl(key, TTL_THRESHOLD, TTL_EXTEND_TO);
+}
+
+pub fn extend_instance(env: &Env) {
+    env.storage()
+        .instance()
+        .extend_ttl(TTL_THRESHOLD, TTL_EXTEND_TO);
+}